@@ -0,0 +1,31 @@
+"""Fetches and prints French day-ahead prices.
+
+Usage: `ENTSOE_API_KEY=... python examples/day_ahead_prices.py [start] [end]`, dates as `yyyyMMdd`, defaults to January
+2025. Requires polars, e.g. `pip install denki-client polars`.
+"""
+
+import asyncio
+import os
+import sys
+
+from denki_client import EntsoeClient
+
+
+async def main(start: str, end: str) -> None:
+    api_key = os.environ.get("ENTSOE_API_KEY")
+    if api_key is None:
+        sys.exit("ENTSOE_API_KEY environment variable is not set")
+
+    async with EntsoeClient(api_key, backend="polars") as client:
+        df = await client.query_day_ahead_price("FR", start=start, end=end)
+
+    if df is None:
+        print(f"No day-ahead prices between {start} and {end}")
+        return
+    print(df.to_native())
+
+
+if __name__ == "__main__":
+    start = sys.argv[1] if len(sys.argv) > 1 else "20250101"
+    end = sys.argv[2] if len(sys.argv) > 2 else "20250201"
+    asyncio.run(main(start, end))
@@ -0,0 +1,195 @@
+/// Returns the name of a `psrType` code, e.g. `B16` -> `Solar`, as in `denki_client.area.PsrType`.
+pub fn psr_type_name(code: &str) -> Option<&'static str> {
+    let name = match code {
+        "A03" => "Mixed",
+        "A04" => "Generation",
+        "A05" => "Load",
+        "B01" => "Biomass",
+        "B02" => "Fossil Brown coal/Lignite",
+        "B03" => "Fossil Coal-derived gas",
+        "B04" => "Fossil Gas",
+        "B05" => "Fossil Hard coal",
+        "B06" => "Fossil Oil",
+        "B07" => "Fossil Oil shale",
+        "B08" => "Fossil Peat",
+        "B09" => "Geothermal",
+        "B10" => "Hydro Pumped Storage",
+        "B11" => "Hydro Run-of-river and poundage",
+        "B12" => "Hydro Water Reservoir",
+        "B13" => "Marine",
+        "B14" => "Nuclear",
+        "B15" => "Other renewable",
+        "B16" => "Solar",
+        "B17" => "Waste",
+        "B18" => "Wind Offshore",
+        "B19" => "Wind Onshore",
+        "B20" => "Other",
+        "B21" => "AC Link",
+        "B22" => "DC Link",
+        "B23" => "Substation",
+        "B24" => "Transformer",
+        "B25" => "Energy storage",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Returns the name of a `businessType` code, e.g. `A62` -> `Spot price`, as in `denki_client.area.BsnType`.
+pub fn business_type_name(code: &str) -> Option<&'static str> {
+    let name = match code {
+        "A01" => "Production",
+        "A04" => "Consumption",
+        "A14" => "Aggregated energy data",
+        "A19" => "Balance energy deviation",
+        "A25" => "General Capacity Information",
+        "A29" => "Already allocated capacity (AAC)",
+        "A37" => "Installed generation",
+        "A43" => "Requested capacity (without price)",
+        "A46" => "System Operator redispatching",
+        "A53" => "Planned maintenance",
+        "A54" => "Unplanned outage",
+        "A60" => "Minimum possible",
+        "A61" => "Maximum possible",
+        "A62" => "Spot price",
+        "A66" => "Energy flow",
+        "A85" => "Internal redispatch",
+        "A91" => "Positive forecast margin (if installed capacity > load forecast)",
+        "A92" => "Negative forecast margin (if load forecast > installed capacity)",
+        "A93" => "Wind generation",
+        "A94" => "Solar generation",
+        "A95" => "Frequency containment reserve",
+        "A96" => "Automatic frequency restoration reserve",
+        "A97" => "Manual frequency restoration reserve",
+        "A98" => "Replacement reserve",
+        "B01" => "Interconnector network evolution",
+        "B02" => "Interconnector network dismantling",
+        "B03" => "Counter trade",
+        "B04" => "Congestion costs",
+        "B05" => "Capacity allocated (including price)",
+        "B07" => "Auction revenue",
+        "B08" => "Total nominated capacity",
+        "B09" => "Net position",
+        "B10" => "Congestion income",
+        "B11" => "Production unit",
+        "B33" => "Area Control Error",
+        "B74" => "Offer",
+        "B75" => "Need",
+        "B95" => "Procured capacity",
+        "C22" => "Shared Balancing Reserve Capacity",
+        "C23" => "Share of reserve capacity",
+        "C24" => "Actual reserve capacity",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Returns the name of a `processType` code, e.g. `A16` -> `Realised`, as in `denki_client.area.ProcessType`.
+pub fn process_type_name(code: &str) -> Option<&'static str> {
+    let name = match code {
+        "A01" => "Day ahead",
+        "A02" => "Intra day incremental",
+        "A16" => "Realised",
+        "A18" => "Intraday total",
+        "A31" => "Week ahead",
+        "A32" => "Month ahead",
+        "A33" => "Year ahead",
+        "A39" => "Synchronisation process",
+        "A40" => "Intraday process",
+        "A46" => "Replacement reserve",
+        "A47" => "Manual frequency restoration reserve",
+        "A51" => "Automatic frequency restoration reserve",
+        "A52" => "Frequency containment reserve",
+        "A56" => "Frequency restoration reserve",
+        "A60" => "Scheduled activation mFRR",
+        "A61" => "Direct activation mFRR",
+        "A67" => "Central Selection aFRR",
+        "A68" => "Local Selection aFRR",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Returns the symbol of a `quantity_Measure_Unit.name` or `price_Measure_Unit.name` code, e.g. `MAW` -> `MW`.
+pub fn unit_name(code: &str) -> Option<&'static str> {
+    let name = match code {
+        "KWT" => "kW",
+        "MAW" => "MW",
+        "A90" => "GW",
+        "KWH" => "kWh",
+        "MWH" => "MWh",
+        "GWH" => "GWh",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Dimension and factor to the mega unit of a measure unit code.
+fn unit_scale(code: &str) -> Option<(&'static str, f64)> {
+    let scale = match code {
+        "KWT" => ("power", 1e-3),
+        "MAW" => ("power", 1.0),
+        "A90" => ("power", 1e3),
+        "KWH" => ("energy", 1e-3),
+        "MWH" => ("energy", 1.0),
+        "GWH" => ("energy", 1e3),
+        _ => return None,
+    };
+    Some(scale)
+}
+
+/// Converts a value between two measure unit codes of the same dimension, e.g. `MAW` to `A90` (MW to GW).
+///
+/// Returns `None` for unknown codes or for a power converted to an energy, which would need a duration.
+pub fn convert_unit(value: f64, from: &str, to: &str) -> Option<f64> {
+    let (from_dimension, from_factor) = unit_scale(from)?;
+    let (to_dimension, to_factor) = unit_scale(to)?;
+    if from_dimension != to_dimension {
+        return None;
+    }
+    Some(value * from_factor / to_factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{business_type_name, convert_unit, process_type_name, psr_type_name, unit_name};
+
+    #[test]
+    fn test_psr_type_name() {
+        assert_eq!(psr_type_name("B16"), Some("Solar"));
+        assert_eq!(psr_type_name("B19"), Some("Wind Onshore"));
+        assert_eq!(psr_type_name("Z99"), None);
+    }
+
+    #[test]
+    fn test_business_type_name() {
+        assert_eq!(business_type_name("A62"), Some("Spot price"));
+        assert_eq!(
+            business_type_name("A96"),
+            Some("Automatic frequency restoration reserve")
+        );
+        assert_eq!(business_type_name("B16"), None);
+    }
+
+    #[test]
+    fn test_unit_name() {
+        assert_eq!(unit_name("MAW"), Some("MW"));
+        assert_eq!(unit_name("MWH"), Some("MWh"));
+        assert_eq!(unit_name("EUR"), None);
+    }
+
+    #[test]
+    fn test_convert_unit() {
+        assert_eq!(convert_unit(1500.0, "MAW", "A90"), Some(1.5));
+        assert_eq!(convert_unit(2.0, "GWH", "MWH"), Some(2000.0));
+        assert_eq!(convert_unit(61250.0, "MAW", "KWT"), Some(61250000.0));
+        assert_eq!(convert_unit(1.0, "MAW", "MWH"), None);
+        assert_eq!(convert_unit(1.0, "MAW", "XXX"), None);
+    }
+
+    #[test]
+    fn test_process_type_name() {
+        assert_eq!(process_type_name("A01"), Some("Day ahead"));
+        assert_eq!(process_type_name("A16"), Some("Realised"));
+        assert_eq!(process_type_name("A62"), None);
+    }
+}
@@ -1,7 +1,7 @@
 import importlib.metadata
 
 from denki_client.area import Area
-from denki_client.entsoe import EntsoeClient
+from denki_client.entsoe import EntsoeClient, fetch_and_parse
 
-__all__ = ["Area", "EntsoeClient"]
+__all__ = ["Area", "EntsoeClient", "fetch_and_parse"]
 __version__ = importlib.metadata.version("denki_client")
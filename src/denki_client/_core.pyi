@@ -1,11 +1,508 @@
+from collections.abc import Callable
+from datetime import datetime
+from typing import Literal, TypedDict, Unpack
+
+from denki_client.exceptions import DenkiHttpError as DenkiHttpError
+from denki_client.exceptions import DenkiParseError as DenkiParseError
+from denki_client.exceptions import DenkiRateLimitedError as DenkiRateLimitedError
+from denki_client.exceptions import DenkiUnsupportedError as DenkiUnsupportedError
+
+Data = float | int | str | datetime | None
+"""Parsed value: `timestamp` columns hold UTC datetimes (local ones with `output_tz`, str with `iso_timestamps`), labels
+are parsed as int, else float, else str. Points missing a label hold None for it."""
+
+class Period(TypedDict):
+    start: datetime
+    end: datetime
+
+class TimeSeries(TypedDict):
+    mrid: str | None
+    business_type: str | None
+    in_domain: str | None
+    out_domain: str | None
+    currency: str | None
+    measure_unit: str | None
+    curve_type: str | None
+    psr_type: str | None
+    flow_direction: str | None
+    auction_type: str | None
+    contract_agreement_type: str | None
+    process_type: str | None
+    energy_flow: str | None
+    coding_schemes: dict[str, str]
+    reasons: list[Reason]
+    periods: list[Period]
+    metadata: dict[str, Data]
+    data: dict[str, list[Data]]
+
+class DocumentMetadata(TypedDict):
+    mrid: str | None
+    created_datetime: datetime | None
+    process_type: str | None
+    coding_schemes: dict[str, str]
+
+class ParseStats(TypedDict):
+    series: int
+    periods: int
+    points: int
+    skipped: int
+
+class Document(TypedDict):
+    metadata: DocumentMetadata
+    series: list[TimeSeries]
+    warnings: list[str]
+    stats: ParseStats
+
+class Columns(TypedDict):
+    timestamp: list[datetime]
+    value: list[float]
+
+class Load(TypedDict):
+    process_type: str | None
+    timestamp: list[datetime]
+    quantity: list[int]
+
+class Reservoir(TypedDict):
+    domain: str | None
+    measure_unit: str | None
+    timestamp: list[datetime]
+    stored_energy: list[float]
+
+class GenerationForecast(TypedDict):
+    process_type: str | None
+    series: dict[str, Columns]
+    timestamp: list[datetime]
+    aligned: dict[str, list[Data]]
+
+class ImbalancePrices(TypedDict):
+    currency: str | None
+    measure_unit: str | None
+    directions: dict[str, Columns]
+
+class Flow(TypedDict):
+    out_domain: str | None
+    in_domain: str | None
+    timestamp: list[datetime]
+    value: list[float]
+
+class TransferCapacity(TypedDict):
+    out_domain: str | None
+    in_domain: str | None
+    contract_agreement_type: str | None
+    timestamp: list[datetime]
+    value: list[float]
+
+class ActivatedEnergy(TypedDict):
+    flow_direction: str | None
+    business_type: str | None
+    measure_unit: str | None
+    timestamp: list[datetime]
+    value: list[float]
+
+class Flows(TypedDict):
+    directions: list[Flow]
+    net: list[Flow]
+
+class Reason(TypedDict):
+    code: str | None
+    text: str | None
+
+class Outage(TypedDict):
+    mrid: str | None
+    business_type: str | None
+    asset_mrid: str | None
+    start: datetime
+    end: datetime
+    quantity: float
+    reason: Reason
+
+class ElementNames(TypedDict, total=False):
+    """Names of the elements making up a period, for schemas departing from the `Publication_MarketDocument` ones.
+
+    :param str point: element of each point, defaults to 'Point'
+    :param str position: element of the position of a point, defaults to 'position'
+    :param str resolution: element of the resolution of a period, defaults to 'resolution'
+    """
+
+    point: str
+    position: str
+    resolution: str
+
+class ParseOptions(TypedDict, total=False):
+    """Keyword options of the parsing functions, the flags defaulting to False.
+
+    :param str | None tz: IANA time zone in which daily, monthly and yearly resolutions are stepped, defaults to UTC
+    :param bool sort_points: sort each `TimeSeries` points by timestamp, keeping the last of duplicated timestamps,
+        defaults to True. Only applies to the `Document` parsers, the columns keep the document order
+    :param bool fill_missing: insert None values at the positions missing from a period of an A01 curve, from the first
+        to the last position of its `timeInterval`
+    :param bool validate_points: raise when a period does not hold the points of its `timeInterval` and resolution
+        or, without `fill_missing`, skips a position
+    :param bool iso_timestamps: return timestamps as ISO 8601 strings instead of UTC datetimes
+    :param str | None output_tz: IANA time zone, e.g. 'Europe/Paris', to convert timestamps to as aware datetimes, along
+        with a `utc_offset` column of their offsets in seconds, telling apart the hours repeated when DST ends
+    :param bool lenient: skip the points whose position or label values are not numbers and an invalid header
+        `createdDateTime`, recording them in the `warnings` of a `Document`
+    :param bool decimal_comma: read a ',' in label values as the decimal separator, e.g. '104,98'
+    :param int position_base: number of the first position of a period, defaults to 1, 0 for documents numbering
+        positions from 0
+    :param bool keep_positions: add a `position` column of the position of each point in the document, None for the
+        filled points
+    :param ElementNames element_names: names of the point, position and resolution elements
+    """
+
+    tz: str | None
+    sort_points: bool
+    fill_missing: bool
+    validate_points: bool
+    iso_timestamps: bool
+    output_tz: str | None
+    lenient: bool
+    decimal_comma: bool
+    position_base: int
+    keep_positions: bool
+    element_names: ElementNames
+
 def parse_timeseries_generic(
-    xml_text: str, labels: list[str], metadata: list[str], period_name: str
-) -> dict[str, list[str]]:
+    xml_text: str,
+    labels: list[str],
+    metadata: list[str],
+    period_name: str,
+    **options: Unpack[ParseOptions],
+) -> dict[str, list[Data]]:
     """Parses XML-formatted string to a Python dictionnary.
 
     :param str xml_text: XML-formatted string
-    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag
+    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag, optionally as `{namespace}tag`
+    :param list[str] metadata: list of XML tags to retreive between a `TimeSeries` and a `period_name` tag or inside a
+        point, each point taking the last value read before it in its `TimeSeries`, else the last one of the series,
+        None when the series has none
+    :param str period_name: usually 'period', or 'Available_Period' for capacity and unavailability documents,
+        matched case-insensitively
+    :param ParseOptions options: keyword options of the parsing, see `ParseOptions`
+    :raises NoMatchingDataError: for an `Acknowledgement_MarketDocument`, with the `code` and `text` of its reason
+    :return dict[str, list[Data]]: `timestamp`, `resolution`, labels and metadata columns
+    """
+
+def parse_timeseries_from_path(
+    path: str,
+    labels: list[str],
+    metadata: list[str],
+    period_name: str,
+    **options: Unpack[ParseOptions],
+) -> dict[str, list[Data]]:
+    """Parses an XML file to a Python dictionnary, streaming it instead of loading it into a string.
+
+    :param str path: path of the XML file
+    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag, optionally as `{namespace}tag`
+    :param list[str] metadata: list of XML tags to retreive between a `TimeSeries` and a `period_name` tag or inside a
+        point, each point taking the last value read before it in its `TimeSeries`, else the last one of the series,
+        None when the series has none
+    :param str period_name: usually 'period'
+    :param ParseOptions options: keyword options of the parsing, see `ParseOptions`
+    :return dict[str, list[Data]]: `timestamp`, `resolution`, labels and metadata columns
+    """
+
+def parse_timeseries_from_bytes(
+    data: bytes,
+    labels: list[str],
+    metadata: list[str],
+    period_name: str,
+    **options: Unpack[ParseOptions],
+) -> dict[str, list[Data]]:
+    """Parses XML-formatted bytes to a Python dictionnary, decoded according to their XML declaration.
+
+    :param bytes data: XML-formatted bytes, e.g. a response content
+    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag, optionally as `{namespace}tag`
+    :param list[str] metadata: list of XML tags to retreive between a `TimeSeries` and a `period_name` tag or inside a
+        point, each point taking the last value read before it in its `TimeSeries`, else the last one of the series,
+        None when the series has none
+    :param str period_name: usually 'period'
+    :param ParseOptions options: keyword options of the parsing, see `ParseOptions`
+    :return dict[str, list[Data]]: `timestamp`, `resolution`, labels and metadata columns
+    """
+
+def parse_timeseries_document(
+    xml_text: str,
+    labels: list[str],
+    metadata: list[str],
+    period_name: str,
+    **options: Unpack[ParseOptions],
+) -> Document:
+    """Parses XML-formatted string to a Python dictionnary, keeping each `TimeSeries` apart.
+
+    :param str xml_text: XML-formatted string
+    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag, optionally as `{namespace}tag`
     :param list[str] metadata: list of XML tags to retreive between a `TimeSeries` and a `period_name` tag
     :param str period_name: usually 'period'
-    :return dict[str, list[str]]:
+    :param ParseOptions options: keyword options of the parsing, see `ParseOptions`
+    :return Document: `metadata` of the document, one dictionnary per `TimeSeries`, see `TimeSeries`, and the
+        `warnings` of lenient parsing, along with the `stats` counts of series, periods, points and skipped points
+    """
+
+def parse_timeseries_multidoc(
+    xml_text: str,
+    labels: list[str],
+    metadata: list[str],
+    period_name: str,
+    **options: Unpack[ParseOptions],
+) -> Document:
+    """Same as `parse_timeseries_document`, for several documents concatenated in one string, whose series are merged.
+
+    :param str xml_text: XML-formatted documents, split on their XML declaration
+    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag, optionally as `{namespace}tag`
+    :param list[str] metadata: list of XML tags to retreive between a `TimeSeries` and a `period_name` tag
+    :param str period_name: usually 'period'
+    :param ParseOptions options: keyword options of the parsing, see `ParseOptions`
+    :return Document: `metadata` of the first document, the `TimeSeries` of every document in order, their `warnings`
+        and summed `stats`
+    """
+
+def parse_timeseries_multi(
+    xml_text: str,
+    labels: list[str],
+    period_name: str,
+    separator: str = "_",
+    series_index: bool = False,
+    positions: bool = False,
+) -> dict[str, list[Data]]:
+    """Parses several labels of a XML-formatted string in a single pass, per resolution.
+
+    :param str xml_text: XML-formatted string
+    :param list[str] labels: list of XML tags to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :param str separator: between the parts of a key, defaults to '_'
+    :param bool series_index: prefix the keys with the index of their `TimeSeries`, e.g. `0_PT60M_timestamp`, so that
+        series sharing a resolution are not merged, defaults to False
+    :param bool positions: add the `{resolution}_position` columns of the `<position>` of each point in the document, None
+        for gap-filled points, defaults to False
+    :return dict[str, list[Data]]: `{resolution}_timestamp` and `{resolution}_{label}` columns, None where a point
+        misses a label
+    """
+
+def for_each_point(
+    xml_text: str, label: str, period_name: str, callback: Callable[[str, datetime, Data], bool | None]
+) -> None:
+    """Calls `callback(resolution, timestamp, value)` on each point as it is parsed, without accumulating the points.
+
+    :param str xml_text: XML-formatted string
+    :param str label: XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :param Callable[[str, datetime, Data], bool | None] callback: returns False to stop parsing. An exception raised
+        by the callback stops parsing and is propagated.
+    """
+
+def parse_timeseries_columns(xml_text: str, label: str, period_name: str) -> dict[str, Columns]:
+    """Parses XML-formatted string to typed columns per resolution.
+
+    :param str xml_text: XML-formatted string
+    :param str label: numeric XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :return dict[str, Columns]: for each resolution, aligned `timestamp` (datetime) and `value` (float) lists
+    """
+
+def parse_timeseries_nth(xml_text: str, label: str, period_name: str, index: int) -> TimeSeries:
+    """Parses the `TimeSeries` at `index` of an XML-formatted string, skipping the points of the other series.
+
+    :param str xml_text: XML-formatted string
+    :param str label: XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :param int index: index of the series in the document, raising when the document holds fewer series
+    :return TimeSeries: the series at `index`
+    """
+
+def parse_timeseries_resampled(
+    xml_text: str, label: str, period_name: str, aggregation: Literal["mean", "sum"] = "mean"
+) -> Columns:
+    """Parses XML-formatted string onto its coarsest resolution, e.g. four PT15M points into one PT60M value.
+
+    :param str xml_text: XML-formatted string
+    :param str label: numeric XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :param str aggregation: 'mean' or 'sum' of the finer points falling in a coarser step
+    :return Columns: aligned `timestamp` (datetime) and `value` (float) lists, raising when the coarsest resolution is
+        not an integer multiple of the others
+    """
+
+def parse_timeseries_records(xml_text: str, label: str, period_name: str) -> list[dict[str, Data]]:
+    """Parses XML-formatted string to one record per point, e.g. for `pandas.DataFrame.from_records`.
+
+    :param str xml_text: XML-formatted string
+    :param str label: XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :return list[dict[str, Data]]: `timestamp`, `resolution` and `value` of each point, in document order
+    """
+
+def parse_auto(xml_text: str) -> dict[str, list[Data]]:
+    """Parses XML-formatted string to a Python dictionnary, with the label and period name of its document type.
+
+    Raises for unknown document types, to parse with `parse_timeseries_generic` instead.
+
+    :param str xml_text: XML-formatted string of a known document type, e.g. `A44` prices or `A75` generation
+    :return dict[str, list[Data]]: same as `parse_timeseries_generic`
+    """
+
+def parse_timeseries_to_csv(xml_text: str, label: str, period_name: str) -> str:
+    """Parses XML-formatted string to CSV, e.g. to dump prices to a file.
+
+    :param str xml_text: XML-formatted string
+    :param str label: XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :return str: `timestamp,value` header and one row per point with RFC 3339 timestamps, with a `resolution` column
+        when the document has several resolutions
+    """
+
+def parse_timeseries_by_psr_type(xml_text: str, label: str, period_name: str) -> dict[str, Columns]:
+    """Parses XML-formatted string to typed columns per production type, e.g. of a generation per type document.
+
+    :param str xml_text: XML-formatted string
+    :param str label: numeric XML tag to retreive inside a `period_name` tag
+    :param str period_name: usually 'period'
+    :return dict[str, Columns]: for each `psrType` code, aligned `timestamp` (datetime) and `value` (float) lists,
+        consumption series (`outBiddingZone_Domain`) e.g. of pumped storage being keyed `{psrType}_consumption`
+    """
+
+def parse_load(xml_text: str) -> Load:
+    """Parses XML-formatted string of a load document, e.g. system total load.
+
+    :param str xml_text: XML-formatted string
+    :return Load: `process_type` of the document (e.g. 'A01' for day-ahead forecasts or 'A16' for realised values),
+        with aligned `timestamp` (datetime) and `quantity` (int) lists
+    """
+
+def parse_total_load_forecast(xml_text: str) -> Load:
+    """Parses the day-ahead total load forecast (A65 with processType A01) of XML-formatted documents.
+
+    :param str xml_text: XML-formatted string, possibly of several concatenated documents
+    :return Load: see `parse_load`, without the series of other processes, e.g. realised values
+    """
+
+def parse_actual_total_load(xml_text: str) -> Load:
+    """Parses the actual total load (A65 with processType A16) of XML-formatted documents.
+
+    :param str xml_text: XML-formatted string, possibly of several concatenated documents
+    :return Load: see `parse_load`, without the series of other processes, e.g. day-ahead forecasts
+    """
+
+def parse_reservoir(xml_text: str) -> Reservoir:
+    """Parses XML-formatted string of a reservoir filling document (A72), reporting the stored energy per week.
+
+    :param str xml_text: XML-formatted string
+    :return Reservoir: bidding zone `domain` and `measure_unit` (e.g. 'MWH') of the document, with aligned weekly
+        `timestamp` (datetime) and `stored_energy` (float) lists
+    """
+
+def parse_net_transfer_capacity(xml_text: str) -> list[TransferCapacity]:
+    """Parses XML-formatted string of a net transfer capacity document, e.g. forecasted (A61) or offered (A31) capacity.
+
+    :param str xml_text: XML-formatted string
+    :return list[TransferCapacity]: one capacity per direction and `contract_agreement_type` (e.g. 'A01' for day-ahead),
+        with aligned `timestamp` (datetime) and `value` (float) lists, in document order
+    """
+
+def parse_activated_energy(xml_text: str) -> list[ActivatedEnergy]:
+    """Parses XML-formatted string of an activated balancing energy document (A83/A84).
+
+    :param str xml_text: XML-formatted string
+    :return list[ActivatedEnergy]: one series per `flow_direction` ('A01' up, 'A02' down) and reserve `business_type`
+        (e.g. 'A96' for aFRR), with aligned `timestamp` (datetime) and `value` (float) lists, in document order
+    """
+
+def parse_generation_forecast(xml_text: str, align: bool = False) -> GenerationForecast:
+    """Parses XML-formatted string of a wind and solar forecast document to forecasts per production type.
+
+    :param str xml_text: XML-formatted string
+    :param bool align: also lay the forecasts out on the timestamps shared by every production type, defaults to False
+    :return GenerationForecast: `process_type` of the document and `series` columns keyed by `psrType` code. With
+        `align`, `aligned` values keyed by `psrType` code on the shared `timestamp` list, None where a type lacks a point
+    """
+
+def parse_installed_capacity(xml_text: str) -> dict[str, Columns]:
+    """Parses XML-formatted string of an installed capacity document to yearly capacities per production type.
+
+    :param str xml_text: XML-formatted string
+    :return dict[str, Columns]: for each `psrType` code, aligned yearly `timestamp` (datetime) and `value` (float) lists,
+        summed over the units of a production type
+    """
+
+def parse_imbalance_prices(xml_text: str) -> ImbalancePrices:
+    """Parses XML-formatted string of an imbalance price document to prices per flow direction.
+
+    :param str xml_text: XML-formatted string
+    :return ImbalancePrices: shared `currency` and `measure_unit`, with `directions` columns keyed by
+        `flowDirection.direction` code ('A01' up, 'A02' down)
+    """
+
+def parse_scheduled_exchanges(xml_text: str) -> list[Flow]:
+    """Parses XML-formatted string of a scheduled commercial exchanges document to schedules per direction.
+
+    :param str xml_text: XML-formatted string
+    :return list[Flow]: one schedule per direction, from `out_domain` to `in_domain`, in document order
+    """
+
+def parse_flows(xml_text: str, label: str, period_name: str) -> Flows:
+    """Parses XML-formatted string of a cross-border document to flows per direction, from `out_domain` to `in_domain`.
+
+    :param str xml_text: XML-formatted string
+    :param str label: numeric XML tag to retreive inside a `period_name` tag, usually 'quantity'
+    :param str period_name: usually 'period'
+    :return Flows: `directions` flows, and `net` flows of the domains with flows in both directions
+    """
+
+def parse_unavailability(xml_text: str) -> list[Outage]:
+    """Parses the outages of an `Unavailability_MarketDocument`, one per `Available_Period` point.
+
+    :param str xml_text: XML-formatted string
+    :return list[Outage]: interval, available `quantity`, `business_type` (A53 planned, A54 forced), `asset_mrid` and
+        `reason` of each outage
+    """
+
+def psr_type_name(code: str) -> str | None:
+    """Returns the name of a `psrType` code, e.g. 'Solar' for 'B16'.
+
+    :param str code: `psrType` code
+    :return str | None: name of the production type, None if the code is unknown
+    """
+
+def business_type_name(code: str) -> str | None:
+    """Returns the name of a `businessType` code, e.g. 'Spot price' for 'A62'.
+
+    :param str code: `businessType` code
+    :return str | None: name of the business type, None if the code is unknown
+    """
+
+def process_type_name(code: str) -> str | None:
+    """Returns the name of a `processType` code, e.g. 'Realised' for 'A16'.
+
+    :param str code: `processType` code
+    :return str | None: name of the process type, None if the code is unknown
+    """
+
+def unit_name(code: str) -> str | None:
+    """Returns the symbol of a `quantity_Measure_Unit.name` or `price_Measure_Unit.name` code, e.g. 'MW' for 'MAW'.
+
+    :param str code: measure unit code
+    :return str | None: symbol of the unit, None if the code is unknown
+    """
+
+def convert_unit(value: float, from_unit: str, to_unit: str) -> float | None:
+    """Converts a value between two measure unit codes of the same dimension, e.g. 'MAW' to 'A90' (MW to GW).
+
+    :param float value: value in `from_unit`
+    :param str from_unit: measure unit code, e.g. 'MAW', 'MWH'
+    :param str to_unit: measure unit code, e.g. 'A90', 'GWH'
+    :return float | None: value in `to_unit`, None for unknown codes or units of different dimensions (power, energy)
+    """
+
+def parse_acknowledgement(xml_text: str) -> Reason | None:
+    """Parses the `Reason` of an `Acknowledgement_MarketDocument`.
+
+    :param str xml_text: XML-formatted string
+    :return Reason | None: `code` and `text` of the reason, None if the document is not an acknowledgement
+    """
+
+def check_status(status: int) -> None:
+    """Raises the error of an unsuccessful HTTP response status.
+
+    :param int status: HTTP status of the response
     """
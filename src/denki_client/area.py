@@ -48,10 +48,21 @@ class Area(enum.Enum):
     def code(self):
         return self.value
 
+    @property
+    def eic_code(self) -> str:
+        return self.value
+
     @classmethod
     def has_code(cls, code: str) -> bool:
         return code in cls.__members__
 
+    @classmethod
+    def from_name(cls, name: str) -> Area:
+        """Get an area from its name e.g. `FR`, `DE_LU` or `NO_2`, case insensitive."""
+        if not cls.has_code(name.upper()):
+            raise ValueError(f"Invalid area name, got {name=}")
+        return cls[name.upper()]
+
     # List taken directly from the API Docs
     # fmt: off
     DE_50HZ =        "10YDE-VE-------2", "50Hertz CA, DE(50HzT) BZA",                                  "Europe/Berlin",
@@ -217,12 +228,15 @@ class BsnType(enum.Enum):
     A19 = "Balance energy deviation"
     A25 = "General Capacity Information"
     A29 = "Already allocated capacity (AAC)"
+    A37 = "Installed generation"
     A43 = "Requested capacity (without price)"
     A46 = "System Operator redispatching"
     A53 = "Planned maintenance"
     A54 = "Unplanned outage"
     A60 = "Minimum possible"
     A61 = "Maximum possible"
+    A62 = "Spot price"
+    A66 = "Energy flow"
     A85 = "Internal redispatch"
     A91 = "Positive forecast margin (if installed capacity > load forecast)"
     A92 = "Negative forecast margin (if load forecast > installed capacity)"
@@ -307,6 +321,15 @@ class DocumentType(enum.Enum):
     B17 = "Aggregated netted external TSO schedule document"
     B45 = "Bid Availability Document"
 
+    def as_code(self) -> str:
+        return self.name
+
+    @classmethod
+    def from_code(cls, code: str) -> DocumentType:
+        if code not in cls.__members__:
+            raise ValueError(f"Invalid document type code, got {code=}")
+        return cls[code]
+
 
 class ProcessType(enum.Enum):
     A01 = "Day ahead"
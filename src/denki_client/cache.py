@@ -0,0 +1,34 @@
+import hashlib
+import time
+from datetime import timedelta
+from pathlib import Path
+
+
+class ResponseCache:
+    def __init__(self, directory: str | Path, ttl: timedelta | None = None) -> None:
+        """On-disk cache of response contents, keyed by query parameters.
+
+        :param str | Path directory: directory of the cached contents, created if needed
+        :param timedelta | None ttl: time after which a cached content expires, defaults to None (never expires)
+        """
+        self.directory = Path(directory)
+        self.directory.mkdir(parents=True, exist_ok=True)
+        self.ttl = ttl
+
+    def _path(self, params: dict[str, str]) -> Path:
+        # The security token does not change the content, and must not be written to disk.
+        query = "&".join(f"{k}={v}" for k, v in sorted(params.items()) if k != "securityToken")
+        return self.directory / f"{hashlib.sha256(query.encode()).hexdigest()}.bin"
+
+    def get(self, params: dict[str, str]) -> bytes | None:
+        """Get the cached content of a query, None if missing or expired."""
+        path = self._path(params)
+        if not path.is_file():
+            return None
+        if self.ttl is not None and time.time() - path.stat().st_mtime > self.ttl.total_seconds():
+            return None
+        return path.read_bytes()
+
+    def set(self, params: dict[str, str], content: bytes) -> None:
+        """Cache the content of a query."""
+        self._path(params).write_bytes(content)
@@ -1,15 +1,28 @@
+import asyncio
+import enum
 import logging
-from datetime import datetime
+from collections.abc import Awaitable, Callable
+from dataclasses import dataclass
+from datetime import datetime, timedelta
+from pathlib import Path
 from types import ModuleType
-from typing import Literal
+from typing import Literal, TypeVar
 
 import httpx
 import narwhals as nw
-from tenacity import retry, retry_if_exception_type, stop_after_attempt, wait_fixed
+from tenacity import AsyncRetrying, retry_if_exception, stop_after_attempt, wait_exponential, wait_random
 
 from denki_client._core import parse_timeseries_generic
-from denki_client.area import Area, BusinessType, FlowDirection, PsrType
-from denki_client.exceptions import raise_response_error
+from denki_client.area import Area, BusinessType, DocumentType, FlowDirection, Neighbours, PsrType, lookup_area
+from denki_client.cache import ResponseCache
+from denki_client.exceptions import (
+    DenkiTimeoutError,
+    EntsoeResponseError,
+    InvalidParameterError,
+    raise_response_error,
+)
+from denki_client.parsers import is_data_document, split_documents
+from denki_client.rate_limiter import RateLimiter
 from denki_client.schemas import (
     ACTIVATED_BALANCING_ENERGY_PRICE_SCHEMA,
     ACTIVATED_BALANCING_ENERGY_VOLUME_SCHEMA,
@@ -21,28 +34,218 @@ from denki_client.schemas import (
 )
 from denki_client.utils import documents_limited, inclusive, parse_inputs, split_query
 
+RETRYABLE_STATUS_CODES = {429, 502, 503, 504}
+DEFAULT_TIMEOUT = httpx.Timeout(30.0, connect=10.0)
+# Document types published per area, queried with it as `in_Domain`/`out_Domain`.
+AREA_DOCUMENT_TYPES = frozenset(
+    {
+        DocumentType.A44,
+        DocumentType.A65,
+        DocumentType.A68,
+        DocumentType.A69,
+        DocumentType.A70,
+        DocumentType.A71,
+        DocumentType.A72,
+        DocumentType.A73,
+        DocumentType.A75,
+        DocumentType.A83,
+        DocumentType.A84,
+        DocumentType.A85,
+        DocumentType.A86,
+    }
+)
+# Document types published per border, for the areas with neighbours.
+BORDER_DOCUMENT_TYPES = frozenset({DocumentType.A09, DocumentType.A11, DocumentType.A61})
+
+T = TypeVar("T")
+
+
+@dataclass(frozen=True)
+class RawResponse:
+    """Response of `EntsoeClient.query_raw`, whatever its status.
+
+    :param int status: HTTP status code
+    :param httpx.Headers headers: response headers, looked up case-insensitively e.g. `headers["content-type"]`
+    :param bytes body: response body, a ZIP archive being kept as is
+    """
+
+    status: int
+    headers: httpx.Headers
+    body: bytes
+
 
 class EntsoeClient:
-    def __init__(self, api_key: str, backend: ModuleType | nw.Implementation | str, **httpx_client_kwargs) -> None:
+    def __init__(
+        self,
+        api_key: str,
+        backend: ModuleType | nw.Implementation | str,
+        base_url: str = "https://web-api.tp.entsoe.eu/api",
+        rate_limit: int | None = 400,
+        max_attempts: int = 3,
+        retry_base_delay: float = 2.0,
+        retry_jitter: float = 1.0,
+        timeout: float | httpx.Timeout | None = None,
+        http_client: httpx.AsyncClient | None = None,
+        **httpx_client_kwargs,
+    ) -> None:
         """Client to ENTSO-e API.
 
-        :param str api_key: API key obtained by creating an account on the website.
+        :param str api_key: API key obtained by creating an account on the website, must not be empty.
         :param ModuleType | Implementation | str backend: Narwhals's compatible backend.
-        :param dict httpx_client_kwargs: Additional keyword arguments to pass to the httpx client.
+        :param str base_url: URL of the API, e.g. of a mock server, defaults to the production endpoint.
+        :param int | None rate_limit: maximum number of requests per minute, defaults to 400 as allowed by ENTSO-e.
+            None disables the rate limiting.
+        :param int max_attempts: attempts of a request failing on a connection error, a timeout or a 429, 502, 503 or
+            504 status, defaults to 3. Other error statuses fail immediately.
+        :param float retry_base_delay: in seconds, doubled after each failed attempt, defaults to 2.0.
+        :param float retry_jitter: in seconds, maximum random delay added to each retry, defaults to 1.0.
+        :param float | httpx.Timeout | None timeout: in seconds, of each request, defaults to None: 10 s to connect and
+            30 s to read, write or wait for a pooled connection. A request timing out is retried as a connection error,
+            raising a `DenkiTimeoutError` once attempts are exhausted.
+        :param httpx.AsyncClient | None http_client: existing client to send the requests with, e.g. to share its
+            connection pool, timeouts or TLS settings. It is used as is and left open by `aclose`. Defaults to None, a
+            client being created from `httpx_client_kwargs`.
+        :param dict httpx_client_kwargs: Additional keyword arguments to pass to the httpx client, e.g. `proxy`.
+
+        Requests are non-blocking and share the connection pool of a single `httpx.AsyncClient`, so queries can be
+        run concurrently, e.g. with `asyncio.gather`. Use the client as an async context manager, or call `aclose`,
+        to release the pool.
+
+        Responses are requested gzip-compressed and decompressed by httpx before being parsed, ZIP archives are still
+        detected by their magic bytes.
 
         API doc: `https://documenter.getpostman.com/view/7009892/2s93JtP3F6`.
         """
+        if not api_key.strip():
+            raise InvalidParameterError("api_key is empty, set the security token of your ENTSO-e account")
         self.api_key = api_key
-        self.base_url = "https://web-api.tp.entsoe.eu/api"
-        self.session = httpx.AsyncClient(**httpx_client_kwargs)
+        self.base_url = base_url
+        if http_client is not None and (httpx_client_kwargs or timeout is not None):
+            raise TypeError("httpx client keyword arguments cannot be combined with an existing `http_client`")
+        if http_client is None:
+            headers = {"Accept-Encoding": "gzip, deflate", **httpx_client_kwargs.pop("headers", {})}
+            timeout = DEFAULT_TIMEOUT if timeout is None else httpx.Timeout(timeout)
+            http_client = httpx.AsyncClient(headers=headers, timeout=timeout, **httpx_client_kwargs)
+            self.owns_session = True
+        else:
+            self.owns_session = False
+        self.session = http_client
         self.logger = logging.getLogger(__name__)
         self.backend = backend
+        self.rate_limiter = RateLimiter(rate_limit) if rate_limit is not None else None
+        self.max_attempts = max_attempts
+        self.retry_base_delay = retry_base_delay
+        self.retry_jitter = retry_jitter
+        self.cache: ResponseCache | None = None
+
+    def with_cache(self, directory: str | Path, ttl: timedelta | None = None) -> "EntsoeClient":
+        """Cache the successful responses on disk, keyed by their query parameters.
+
+        :param str | Path directory: directory of the cached responses
+        :param timedelta | None ttl: time after which a cached response is requested again, defaults to None (never).
+            Set it for queries of recent data, which may still be updated.
+        :return EntsoeClient: the client itself
+        """
+        self.cache = ResponseCache(directory, ttl)
+        return self
+
+    @staticmethod
+    def available_document_types(area: Area | str) -> set[DocumentType]:
+        """Document types that can be queried and parsed for an area, e.g. to present valid choices.
+
+        They come from a static table, without requesting the API: an area may still lack data for some of them.
+
+        :param Area | str area:
+        :return set[DocumentType]: per-area types, along with the cross-border ones if the area has neighbours
+        """
+        area = lookup_area(area)
+        document_types = set(AREA_DOCUMENT_TYPES)
+        if area.name in Neighbours.__members__:
+            document_types |= BORDER_DOCUMENT_TYPES
+        return document_types
+
+    async def __aenter__(self) -> "EntsoeClient":
+        return self
+
+    async def __aexit__(self, *args) -> None:
+        await self.aclose()
+
+    async def aclose(self) -> None:
+        """Close the connection pool, unless the client was given an existing `http_client`."""
+        if self.owns_session:
+            await self.session.aclose()
+
+    @staticmethod
+    def _is_retryable(error: BaseException) -> bool:
+        if isinstance(error, EntsoeResponseError):
+            return error.status_code in RETRYABLE_STATUS_CODES
+        return isinstance(error, (httpx.ConnectError, httpx.TimeoutException))
+
+    async def _get(self, params: dict) -> httpx.Response:
+        """Get request on the API, `securityToken` is set automatically.
+
+        Requests are paced by the rate limiter and retried with an exponential backoff, see `EntsoeClient`.
+
+        :param dict params: parameters dictionnary. See documentation for more details.
+        :return httpx.Response:
+        """
+        params = self._query_params(params)
+        if self.cache is not None and (content := self.cache.get(params)) is not None:
+            self.logger.debug(f"Cached response for {params=}")
+            return httpx.Response(200, content=content, request=httpx.Request("GET", self.base_url, params=params))
+
+        async def send() -> httpx.Response:
+            response = await self.session.get(self.base_url, params=params)
+            return raise_response_error(response)
+
+        response = await self._send(params, send)
+        if self.cache is not None and is_data_document(response.content):
+            self.cache.set(params, response.content)
+        return response
+
+    def _query_params(self, params: dict) -> dict:
+        """Add the `securityToken` and format the enum parameters, dropping None ones."""
+        params = {**params, "securityToken": self.api_key}
+        return {k: self._format_param(v) for k, v in params.items() if v is not None}
+
+    async def _send(self, params: dict, send: Callable[[], Awaitable[T]]) -> T:
+        """Run a request, paced by the rate limiter and retried with an exponential backoff, see `EntsoeClient`.
+
+        :param dict params: formatted query parameters, for logging
+        :param Callable[[], Awaitable[T]] send: one attempt of the request
+        :return T: result of the successful attempt
+        """
+        attempts = 0
+        try:
+            async for attempt in AsyncRetrying(
+                retry=retry_if_exception(self._is_retryable),
+                stop=stop_after_attempt(self.max_attempts),
+                wait=wait_exponential(multiplier=self.retry_base_delay) + wait_random(0, self.retry_jitter),
+                reraise=True,
+            ):
+                with attempt:
+                    attempts += 1
+                    if self.rate_limiter is not None:
+                        await self.rate_limiter.acquire()
+                    self.logger.debug(f"Request with {params=}")
+                    result = await send()
+        except EntsoeResponseError as e:
+            if attempts == 1:
+                raise
+            raise EntsoeResponseError(e.response, attempts) from e
+        except httpx.TimeoutException as e:
+            raise DenkiTimeoutError(e, attempts) from e
+        return result
+
+    @staticmethod
+    def _format_param(value):
+        """Format an enum parameter to its ENTSO-e code."""
+        if isinstance(value, Area):
+            return value.code
+        if isinstance(value, enum.Enum):
+            return value.name
+        return value
 
-    @retry(
-        retry=retry_if_exception_type((httpx.ConnectError, httpx.ReadTimeout)),
-        stop=stop_after_attempt(3),
-        wait=wait_fixed(2),
-    )
     async def _base_request(self, params: dict, start_str: str, end_str: str) -> httpx.Response:
         """Base Request.
 
@@ -51,17 +254,169 @@ class EntsoeClient:
         :param str end_str: Pattern yyyyMMddHHmm e.g. 201601010000 Considered timezone is the local one.
         :return httpx.Response:
         """
-        base_params = {
-            "securityToken": self.api_key,
-            "periodStart": start_str,
-            "periodEnd": end_str,
-        }
-        params.update(base_params)
-        params = {k: v for k, v in params.items() if v is not None}
-        self.logger.debug(f"Request with {params=}")
-        response = await self.session.get(self.base_url, params=params)
-        raise_response_error(response)
-        return response
+        params.update({"periodStart": start_str, "periodEnd": end_str})
+        return await self._get(params)
+
+    async def get_document(self, params: dict) -> str:
+        """Get a raw document from the API.
+
+        :param dict params: query parameters e.g. `documentType`, `periodStart`, `periodEnd`. Enums are sent as their code.
+        :raises EntsoeResponseError: if the API responds with an error status, carrying `status_code` and `body`.
+        :return str: XML-formatted document
+        """
+        response = await self._get(params)
+        return response.text
+
+    async def download_to(self, path: str | Path, params: dict) -> tuple[Path, int]:
+        """Save the raw response of the API to a file, e.g. to reproduce a parsing issue.
+
+        The body is streamed to the file rather than held in memory, a ZIP archive being written as is. The cache is
+        bypassed.
+
+        :param str | Path path: file to write, overwritten if it exists
+        :param dict params: query parameters, see `get_document`
+        :raises EntsoeResponseError: if the API responds with an error status, carrying `status_code` and `body`.
+        :return tuple[Path, int]: path of the file and number of bytes written
+        """
+        path = Path(path)
+        params = self._query_params(params)
+
+        async def send() -> int:
+            async with self.session.stream("GET", self.base_url, params=params) as response:
+                if response.is_error:
+                    await response.aread()
+                    raise_response_error(response)
+                size = 0
+                with path.open("wb") as file:
+                    async for chunk in response.aiter_bytes():
+                        size += file.write(chunk)
+                return size
+
+        return path, await self._send(params, send)
+
+    async def query_raw(self, params: dict) -> RawResponse:
+        """Query the API without interpreting the response, e.g. to debug an unusual status or content type.
+
+        Error statuses are returned rather than raised or retried. The request is still paced by the rate limiter, and
+        the cache is bypassed.
+
+        :param dict params: query parameters, see `get_document`
+        :return RawResponse: status, headers and body of the response
+        """
+        params = self._query_params(params)
+
+        async def send() -> RawResponse:
+            response = await self.session.get(self.base_url, params=params)
+            return RawResponse(response.status_code, response.headers, response.content)
+
+        return await self._send(params, send)
+
+    @parse_inputs
+    @split_query("1y")
+    async def get_timeseries(
+        self,
+        area: Area | str,
+        document_type: DocumentType | str,
+        *,
+        start: datetime | str,
+        end: datetime | str,
+        labels: list[str],
+        metadata: list[str] | None = None,
+        params: dict | None = None,
+        clip: bool = False,
+    ) -> nw.DataFrame | None:
+        """Query time series of any document type. Ranges exceeding a year are split in one request per year.
+
+        :param Area | str area: sent as `in_Domain` and `out_Domain`, unless overridden in `params`
+        :param DocumentType | str document_type:
+        :param datetime | str start: start of the query
+        :param datetime | str end: end of the query
+        :param list[str] labels: see `parse_timeseries_generic`
+        :param list[str] | None metadata: see `parse_timeseries_generic`, defaults to None
+        :param dict | None params: additional query parameters, defaults to None
+        :param bool clip: drop the points outside of `[start, end)`, e.g. of periods aligned to market time units
+            overlapping the requested range, defaults to False
+        :return nw.DataFrame | None: DataFrame with the `timestamp`, `resolution`, labels and metadata columns
+        """
+        domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
+        params = {"documentType": document_type, "in_Domain": domain_code, "out_Domain": domain_code, **(params or {})}
+        response = await self._base_request(params, start_str, end_str)
+        data = self._parse_response(response, labels, metadata or [], "period")
+        if clip:
+            data = self._clip(data, start, end)
+        if data == {}:
+            return None
+        return nw.from_dict(data, backend=self.backend)
+
+    async def fetch_many(
+        self,
+        document_type: DocumentType | str,
+        areas: list[Area | str],
+        *,
+        start: datetime | str,
+        end: datetime | str,
+        labels: list[str],
+        concurrency: int = 4,
+        clip: bool = False,
+    ) -> dict[Area | str, nw.DataFrame | Exception | None]:
+        """Query the same time series for several areas concurrently, see `get_timeseries`.
+
+        At most `concurrency` areas are queried at once, their requests being paced by the rate limiter as well. An area
+        without data, i.e. answered with a "No matching data found" acknowledgement, maps to None as with
+        `get_timeseries`. The failure of an area, e.g. an invalid parameter, does not fail the others: its exception is
+        returned in place of its DataFrame.
+
+        :param DocumentType | str document_type:
+        :param list[Area | str] areas: areas to query, each sent as `in_Domain` and `out_Domain`
+        :param datetime | str start: start of the query
+        :param datetime | str end: end of the query
+        :param list[str] labels: see `parse_timeseries_generic`
+        :param int concurrency: maximum number of areas queried at once, defaults to 4
+        :param bool clip: see `get_timeseries`, defaults to False
+        :raises InvalidParameterError: if `concurrency` is lower than 1
+        :return dict[Area | str, nw.DataFrame | Exception | None]: for each area, as given in `areas`, its DataFrame,
+            None without data or the exception raised when querying it
+        """
+        if concurrency < 1:
+            raise InvalidParameterError(f"concurrency must be at least 1, got {concurrency=}")
+        semaphore = asyncio.Semaphore(concurrency)
+
+        async def fetch(area: Area | str) -> nw.DataFrame | None:
+            async with semaphore:
+                return await self.get_timeseries(area, document_type, start=start, end=end, labels=labels, clip=clip)
+
+        results = await asyncio.gather(*(fetch(area) for area in areas), return_exceptions=True)
+        return dict(zip(areas, results))
+
+    def _parse_response(
+        self, response: httpx.Response, labels: list[str], metadata: list[str], period_name: str
+    ) -> dict[str, list]:
+        """Parse every document of a response and merge them in chronological order.
+
+        :param httpx.Response response:
+        :param list[str] labels: see `parse_timeseries_generic`
+        :param list[str] metadata: see `parse_timeseries_generic`
+        :param str period_name: see `parse_timeseries_generic`
+        :return dict[str, list]:
+        """
+        frames = [
+            parse_timeseries_generic(document, labels, metadata, period_name)
+            for document in split_documents(response.content)
+        ]
+        frames = sorted((frame for frame in frames if frame), key=lambda frame: min(frame["timestamp"]))
+        data = {}
+        for frame in frames:
+            for key, values in frame.items():
+                data.setdefault(key, []).extend(values)
+        return data
+
+    @staticmethod
+    def _clip(data: dict[str, list], start: datetime, end: datetime) -> dict[str, list]:
+        """Keep the points whose timestamp is in `[start, end)`, dropping every column if none is left."""
+        kept = [i for i, timestamp in enumerate(data.get("timestamp", [])) if start <= timestamp < end]
+        if not kept:
+            return {}
+        return {key: [values[i] for i in kept] for key, values in data.items()}
 
     def _prepare_inputs(self, area: Area | str, start: datetime | str, end: datetime | str) -> tuple[str, str, str]:
         if isinstance(area, str):
@@ -96,15 +451,15 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A44",
+            "documentType": DocumentType.A44,
             "in_Domain": domain_code,
             "out_Domain": domain_code,
             "contract_MarketAgreement.type": "A01",
             "offset": offset,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["price.amount"],
             ["currency_Unit.name", "price_Measure_Unit.name"],
             "period",
@@ -146,7 +501,7 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A84",
+            "documentType": DocumentType.A84,
             "processType": process_type,
             "controlArea_Domain": domain_code,
             "businessType": business_type,
@@ -155,8 +510,8 @@ class EntsoeClient:
             "originalMarketProduct": None,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["activation_Price.amount"],
             ["flowDirection.direction", "businessType", "currency_Unit.name", "price_Measure_Unit.name"],
             "period",
@@ -208,7 +563,7 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A83",
+            "documentType": DocumentType.A83,
             "processType": process_type,
             "controlArea_Domain": domain_code,
             "businessType": business_type,
@@ -217,8 +572,8 @@ class EntsoeClient:
             "originalMarketProduct": None,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["quantity"],
             ["flowDirection.direction", "businessType", "quantity_Measure_Unit.name"],
             "period",
@@ -263,14 +618,14 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A75",
+            "documentType": DocumentType.A75,
             "processType": "A16",
             "in_Domain": domain_code,
             "psrType": psr_type,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["quantity"],
             ["quantity_Measure_Unit.name", "psrType"],
             "period",
@@ -312,14 +667,14 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A73",
+            "documentType": DocumentType.A73,
             "processType": "A16",
             "in_Domain": domain_code,
             "psrType": psr_type,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["quantity"],
             ["quantity_Measure_Unit.name", "psrType", "production_unit"],
             "period",
@@ -360,14 +715,14 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A68",
+            "documentType": DocumentType.A68,
             "processType": "A33",
             "in_Domain": domain_code,
             "psrType": psr_type,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["quantity"],
             ["quantity_Measure_Unit.name", "psrType"],
             "period",
@@ -410,15 +765,15 @@ class EntsoeClient:
         """
         domain_code, start_str, end_str = self._prepare_inputs(area, start, end)
         params = {
-            "documentType": "A71",
+            "documentType": DocumentType.A71,
             "processType": "A33",
             "in_Domain": domain_code,
             "psrType": psr_type,
             "offset": offset,
         }
         response = await self._base_request(params, start_str, end_str)
-        data = parse_timeseries_generic(
-            response.text,
+        data = self._parse_response(
+            response,
             ["quantity"],
             ["quantity_Measure_Unit.name", "psrType", "production_unit"],
             "period",
@@ -433,3 +788,39 @@ class EntsoeClient:
             )
         )
         return df
+
+
+def fetch_and_parse(
+    api_key: str,
+    document_type: DocumentType | str,
+    area: Area | str,
+    *,
+    start: datetime | str,
+    end: datetime | str,
+    labels: list[str],
+    backend: ModuleType | nw.Implementation | str,
+    clip: bool = False,
+    **client_kwargs,
+) -> nw.DataFrame | None:
+    """Query and parse time series in a single blocking call, see `EntsoeClient.get_timeseries`.
+
+    A client is created and closed for the call, so it must not be called from a running event loop: use
+    `EntsoeClient` there, or to run several queries on a shared connection pool.
+
+    :param str api_key: see `EntsoeClient`
+    :param DocumentType | str document_type:
+    :param Area | str area: sent as `in_Domain` and `out_Domain`
+    :param datetime | str start: start of the query
+    :param datetime | str end: end of the query
+    :param list[str] labels: see `parse_timeseries_generic`
+    :param ModuleType | Implementation | str backend: Narwhals's compatible backend.
+    :param bool clip: drop the points outside of `[start, end)`, see `EntsoeClient.get_timeseries`, defaults to False
+    :param dict client_kwargs: additional keyword arguments of `EntsoeClient`, e.g. `rate_limit`
+    :return nw.DataFrame | None: DataFrame with the `timestamp`, `resolution` and labels columns
+    """
+
+    async def fetch() -> nw.DataFrame | None:
+        async with EntsoeClient(api_key, backend, **client_kwargs) as client:
+            return await client.get_timeseries(area, document_type, start=start, end=end, labels=labels, clip=clip)
+
+    return asyncio.run(fetch())
@@ -31,6 +31,69 @@ class ParseError(Exception):
     pass
 
 
+class EntsoeResponseError(httpx.HTTPStatusError):
+    def __init__(self, response: httpx.Response, attempts: int = 1):
+        after = f" after {attempts} attempts" if attempts > 1 else ""
+        super().__init__(
+            f"ENTSO-e responded with status {response.status_code}{after}: {response.text}",
+            request=response.request,
+            response=response,
+        )
+        self.status_code = response.status_code
+        self.body = response.text
+        self.attempts = attempts
+
+    @classmethod
+    def from_status(cls, status_code: int, message: str):
+        """Error of a bare `status_code`, checked without any response at hand (e.g. by `_core.check_status`)."""
+        error = cls.__new__(cls)
+        httpx.HTTPStatusError.__init__(error, message, request=None, response=None)
+        error.status_code = status_code
+        error.body = ""
+        error.attempts = 1
+        return error
+
+
+class DenkiAuthError(EntsoeResponseError):
+    """ENTSO-e rejected the security token (401 status)."""
+
+    def __init__(self, response: httpx.Response):
+        super().__init__(response)
+        self.args = (
+            "ENTSO-e rejected the request with status 401 Unauthorized: check the security token (API key) of the "
+            "client, which is obtained from the ENTSO-e transparency platform account settings.",
+        )
+
+
+class DenkiHttpError(EntsoeResponseError):
+    """Unsuccessful HTTP status checked by the Rust core, with its `status`."""
+
+    @property
+    def status(self) -> int:
+        return self.status_code
+
+
+class DenkiRateLimitedError(DenkiHttpError):
+    """Too many requests to ENTSO-e (429 status)."""
+
+
+class DenkiParseError(ParseError, ValueError):
+    """Malformed XML document, with the 1-based `line` and `column` of the error and its `message`."""
+
+
+class DenkiUnsupportedError(ParseError, ValueError):
+    """Document the parsers cannot handle, e.g. of an unknown type for `_core.parse_auto`."""
+
+
+class DenkiTimeoutError(Exception):
+    """ENTSO-e did not respond in time, see the `timeout` of `EntsoeClient`."""
+
+    def __init__(self, error: httpx.TimeoutException, attempts: int = 1):
+        after = f" after {attempts} attempts" if attempts > 1 else ""
+        super().__init__(f"ENTSO-e request timed out{after} ({type(error).__name__})")
+        self.attempts = attempts
+
+
 def raise_response_error(response: httpx.Response):
     """Raises correct error from Entsoe server response.
 
@@ -39,11 +102,15 @@ def raise_response_error(response: httpx.Response):
     :raises InvalidBusinessParameterError:
     :raises InvalidPSRTypeError:
     :raises PaginationError:
+    :raises DenkiAuthError: for a 401 status
+    :raises EntsoeResponseError: for any other error status
     :return httpx.Response:
     """
     try:
         response.raise_for_status()
     except httpx.HTTPError as e:
+        if response.status_code == 401:
+            raise DenkiAuthError(response) from e
         error_text = ""
         text_match = re.search(r"<text>(.*?)</text>", response.text, re.DOTALL)
         if text_match:
@@ -76,7 +143,7 @@ def raise_response_error(response: httpx.Response):
                 raise PaginationError(
                     f"The API is limited to {allowed} elements per request. This query requested {requested} documents."
                 )
-        raise e
+        raise EntsoeResponseError(response) from e
     # ENTSO-e has changed their server to also respond with 200 if there is no data but all parameters are valid
     # this means we need to check the contents for this error even when status code 200 is returned
     # to prevent parsing the full response do a text matching instead of full parsing
@@ -1,11 +1,16 @@
+import io
 import re
+import zipfile
 from datetime import UTC, datetime, tzinfo
 
 from dateutil.relativedelta import relativedelta
 from pytz import timezone
 
+from ._core import parse_acknowledgement
 from .exceptions import ParseError, TzNaiveError
 
+ZIP_MAGIC = b"PK\x03\x04"
+
 
 def parse_datetime(date: datetime | str, target_tz: tzinfo | str = UTC) -> datetime:
     """Parse Datetime with timezone.
@@ -43,3 +48,34 @@ def parse_freq(freq: relativedelta | str) -> relativedelta:
             )
         time_params = {name: float(param) for name, param in parts.groupdict().items() if param}
     return relativedelta(**time_params)
+
+
+def split_documents(content: bytes) -> list[str]:
+    """Split a response content into its XML documents.
+
+    Large queries are answered with a ZIP archive of XML documents instead of a single XML body.
+
+    :param bytes content: response content, either a XML document or a ZIP archive
+    :return list[str]: XML-formatted documents
+    """
+    if not content.startswith(ZIP_MAGIC):
+        return [content.decode()]
+    with zipfile.ZipFile(io.BytesIO(content)) as archive:
+        return [archive.read(name).decode() for name in sorted(archive.namelist())]
+
+
+def is_data_document(content: bytes) -> bool:
+    """Whether a response content holds data, i.e. is a ZIP archive or a XML document other than an acknowledgement.
+
+    Only the root element is read, e.g. to keep an `Acknowledgement_MarketDocument` served with a 200 status out of the
+    cache.
+
+    :param bytes content: response content
+    :return bool: False for an acknowledgement or a content that is not XML
+    """
+    if content.startswith(ZIP_MAGIC):
+        return True
+    try:
+        return parse_acknowledgement(content.decode()) is None
+    except ValueError:
+        return False
@@ -0,0 +1,75 @@
+from __future__ import annotations
+
+import enum
+from datetime import UTC, datetime
+
+from denki_client.area import Area, DocumentType, ProcessType, PsrType, lookup_area
+from denki_client.exceptions import InvalidParameterError, TzNaiveError
+
+REQUIRED_PARAMETERS = {
+    "documentType": "document_type",
+    "periodStart": "period_start",
+    "periodEnd": "period_end",
+}
+
+
+class QueryBuilder:
+    """Builds the query parameters of an ENTSO-e request, e.g. for `EntsoeClient.get_document`.
+
+    >>> builder = QueryBuilder().document_type("A44").in_domain("FR").out_domain("FR")
+    >>> params = builder.period_start(start).period_end(end).build()
+    """
+
+    def __init__(self) -> None:
+        self._params: dict[str, str] = {}
+
+    @staticmethod
+    def _format_period(date: datetime) -> str:
+        if date.tzinfo is None:
+            raise TzNaiveError
+        return date.astimezone(UTC).strftime("%Y%m%d%H%M")
+
+    @staticmethod
+    def _format_code(value: enum.Enum | str) -> str:
+        return value.name if isinstance(value, enum.Enum) else value
+
+    def document_type(self, document_type: DocumentType | str) -> QueryBuilder:
+        self._params["documentType"] = self._format_code(document_type)
+        return self
+
+    def in_domain(self, area: Area | str) -> QueryBuilder:
+        self._params["in_Domain"] = lookup_area(area).code
+        return self
+
+    def out_domain(self, area: Area | str) -> QueryBuilder:
+        self._params["out_Domain"] = lookup_area(area).code
+        return self
+
+    def period_start(self, start: datetime) -> QueryBuilder:
+        """:param datetime start: tz-aware start, sent in UTC"""
+        self._params["periodStart"] = self._format_period(start)
+        return self
+
+    def period_end(self, end: datetime) -> QueryBuilder:
+        """:param datetime end: tz-aware end, sent in UTC"""
+        self._params["periodEnd"] = self._format_period(end)
+        return self
+
+    def process_type(self, process_type: ProcessType | str) -> QueryBuilder:
+        self._params["processType"] = self._format_code(process_type)
+        return self
+
+    def psr_type(self, psr_type: PsrType | str) -> QueryBuilder:
+        self._params["psrType"] = self._format_code(psr_type)
+        return self
+
+    def build(self) -> dict[str, str]:
+        """Checks that the required parameters are set.
+
+        :raises InvalidParameterError: naming the first missing required parameter
+        :return dict[str, str]: query parameters
+        """
+        for param, setter in REQUIRED_PARAMETERS.items():
+            if param not in self._params:
+                raise InvalidParameterError(f"missing required query parameter `{param}`, set it with `{setter}`")
+        return dict(self._params)
@@ -0,0 +1,28 @@
+import asyncio
+import time
+
+
+class RateLimiter:
+    def __init__(self, rate: int, period: float = 60.0) -> None:
+        """Token bucket pacing calls to at most `rate` per `period`, allowing bursts of `rate` calls.
+
+        :param int rate: number of calls allowed per `period`
+        :param float period: in seconds, defaults to 60.0
+        """
+        self.capacity = rate
+        self.tokens = float(rate)
+        self.fill_rate = rate / period
+        self.updated_at = time.monotonic()
+        self._lock = asyncio.Lock()
+
+    async def acquire(self) -> None:
+        """Wait until a call is allowed."""
+        async with self._lock:
+            while True:
+                now = time.monotonic()
+                self.tokens = min(self.capacity, self.tokens + (now - self.updated_at) * self.fill_rate)
+                self.updated_at = now
+                if self.tokens >= 1:
+                    self.tokens -= 1
+                    return
+                await asyncio.sleep((1 - self.tokens) / self.fill_rate)
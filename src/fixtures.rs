@@ -0,0 +1,41 @@
+//! Sample documents of the ENTSO-E API, to try the parsers on. Enabled by the `fixtures` feature.
+
+/// Day-ahead prices (`A44`) of one `A03` series, with a lowercased header.
+pub fn day_ahead_price() -> &'static str {
+    include_str!("../fixtures/day_ahead_price.xml")
+}
+
+/// Realised system total load (`A65`, `A16` process).
+pub fn load() -> &'static str {
+    include_str!("../fixtures/load.xml")
+}
+
+/// Actual generation per production type (`A75`), of solar (`B16`) and wind onshore (`B19`).
+pub fn generation() -> &'static str {
+    include_str!("../fixtures/generation.xml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{day_ahead_price, generation, load};
+    use crate::parsers::{parse_load, parse_timeseries_by_psr_type, try_parse_timeseries_document, ParseOptions};
+
+    #[test]
+    fn test_fixtures() {
+        let document = try_parse_timeseries_document(
+            day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "Period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(document.series.len(), 1);
+
+        let load = parse_load(load()).unwrap();
+        assert_eq!(load.quantity.len(), 2);
+
+        let generation = parse_timeseries_by_psr_type(generation(), "quantity", "Period").unwrap();
+        assert_eq!(generation["B19"].value, vec![4021.0, 4187.0]);
+    }
+}
@@ -1,148 +1,2685 @@
-use anyhow::anyhow;
-use jiff::{tz::TimeZone, Span, Timestamp, ToSpan};
-use pyo3::IntoPyObject;
-use std::{collections::HashMap, sync::LazyLock};
-use xml::reader::{EventReader, XmlEvent};
-
-static RESOLUTIONS: LazyLock<HashMap<&'static str, Span>> = LazyLock::new(|| {
-    HashMap::from([
-        ("PT60M", 60.minutes()),
-        ("P1Y", 1.year()),
-        ("PT15M", 15.minutes()),
-        ("PT30M", 30.minutes()),
-        ("P1D", 1.day()),
-        ("P7D", 7.days()),
-        ("P1M", 1.month()),
-        ("PT1M", 1.minute()),
-    ])
-});
-
-#[derive(Clone, Debug, PartialEq, IntoPyObject)]
+use jiff::{fmt::temporal::SpanParser, tz::TimeZone, Span, Timestamp, Zoned};
+#[cfg(feature = "python")]
+use pyo3::{Bound, IntoPyObject, PyAny, PyErr, Python};
+use quick_xml::{
+    encoding::DecodingReader,
+    events::{BytesRef, BytesStart, Event},
+    name::ResolveResult,
+    reader::{NsReader, Reader},
+    XmlVersion,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    ops::ControlFlow,
+};
+
+static SPAN_PARSER: SpanParser = SpanParser::new();
+
+/// Points a period may hold, a year at a minute resolution fitting in.
+const MAX_PERIOD_POINTS: i64 = 1_000_000;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Data {
     F64(f64),
-    ISize(isize),
+    I64(i64),
     Timestamp(Timestamp),
+    /// A timestamp converted to `ParseOptions::output_tz`. Converted to a timezone-aware `datetime` in Python.
+    Zoned(Zoned),
     String(String),
+    /// A missing value, e.g. of a gap-filled position. Converted to `None` in Python.
+    Null,
 }
 
-pub fn parse_timeseries_generic(
-    xml_text: &str,
-    labels: Vec<&str>,
-    metadata: Vec<&str>,
-    period_name: &str,
-) -> Result<HashMap<String, Vec<Data>>, anyhow::Error> {
-    let mut data: HashMap<String, Vec<Data>> = HashMap::new();
-    let parser = EventReader::from_str(xml_text);
+impl Data {
+    /// Compares two values, numbers being equal when within `epsilon` of each other, integers compared as floats.
+    /// Other variants are compared exactly, and `NaN` is never equal, as with the derived `PartialEq`.
+    pub fn approx_eq(&self, other: &Data, epsilon: f64) -> bool {
+        let as_f64 = |data: &Data| match data {
+            Data::F64(value) => Some(*value),
+            Data::I64(value) => Some(*value as f64),
+            _ => None,
+        };
+        match (as_f64(self), as_f64(other)) {
+            (Some(a), Some(b)) => (a - b).abs() <= epsilon,
+            _ => self == other,
+        }
+    }
+}
 
-    let mut current_period_start: Option<String> = None;
-    let mut current_period_resolution: Option<String> = None;
-    let mut current_position: Option<i64> = None;
-    let mut current_label_values: HashMap<String, Data> = HashMap::new();
-    let mut current_metadata_values: HashMap<String, Data> = HashMap::new();
-    let mut current_element: Option<String> = None;
+// Implemented by hand as `IntoPyObject` cannot be derived for the unit `Null` variant.
+#[cfg(feature = "python")]
+impl<'py> IntoPyObject<'py> for Data {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
 
-    for e in parser {
-        match e {
-            Ok(XmlEvent::StartElement { name, .. }) => {
-                current_element = Some(name.local_name.clone());
-                if name.local_name == "TimeSeries" {
-                    current_metadata_values = HashMap::new();
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        match self {
+            Data::F64(value) => Ok(value.into_pyobject(py)?.into_any()),
+            Data::I64(value) => Ok(value.into_pyobject(py)?.into_any()),
+            Data::Timestamp(value) => Ok(value.into_pyobject(py)?.into_any()),
+            Data::Zoned(value) => Ok(value.into_pyobject(py)?.into_any()),
+            Data::String(value) => Ok(value.into_pyobject(py)?.into_any()),
+            Data::Null => Ok(py.None().into_bound(py)),
+        }
+    }
+}
+
+/// Renders floats without trailing zeros (`105`, `104.98`), timestamps as RFC 3339 and `Null` as an empty string.
+impl std::fmt::Display for Data {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Data::F64(value) => write!(f, "{value}"),
+            Data::I64(value) => write!(f, "{value}"),
+            Data::Timestamp(value) => write!(f, "{value}"),
+            Data::Zoned(value) => write!(f, "{value}"),
+            Data::String(value) => write!(f, "{value}"),
+            Data::Null => Ok(()),
+        }
+    }
+}
+
+/// Quotes a CSV field holding a separator, a quote or a line break.
+fn csv_field(data: &Data) -> String {
+    let field = data.to_string();
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Renders aligned `timestamps` and `values` columns, e.g. `{resolution}_timestamp` and `{resolution}_{label}` of
+/// `parse_timeseries_multi`, into `timestamp,value` CSV lines, without header.
+pub fn to_csv_rows(timestamps: &[Data], values: &[Data]) -> Vec<String> {
+    timestamps
+        .iter()
+        .zip(values)
+        .map(|(timestamp, value)| format!("{},{}", csv_field(timestamp), csv_field(value)))
+        .collect()
+}
+
+/// A `TimeSeries` block along with the metadata describing it.
+///
+/// `data` holds the `timestamp`, labels and `resolution` columns of the series' points.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeSeries {
+    pub mrid: Option<String>,
+    pub business_type: Option<String>,
+    pub in_domain: Option<String>,
+    pub out_domain: Option<String>,
+    pub currency: Option<String>,
+    pub measure_unit: Option<String>,
+    pub curve_type: Option<String>,
+    pub psr_type: Option<String>,
+    /// `flowDirection.direction`, `A01` for up and `A02` for down.
+    pub flow_direction: Option<String>,
+    /// `auction.type` of market-coupling documents, e.g. `A01` for implicit auctions.
+    pub auction_type: Option<String>,
+    /// `contract_MarketAgreement.type`, e.g. `A01` for daily contracts.
+    pub contract_agreement_type: Option<String>,
+    /// `process.processType` of the document, telling apart the vintages of a series, e.g. `A01` for day-ahead
+    /// forecasts or `A16` for realised values.
+    pub process_type: Option<String>,
+    /// `generation` for an `inBiddingZone_Domain` series and `consumption` for an `outBiddingZone_Domain` one, e.g. the
+    /// pumping of a pumped-storage `psrType` in generation per type (`A75`) documents.
+    pub energy_flow: Option<String>,
+    /// `codingScheme` attribute of the series identifiers, e.g. `A01` (EIC) for `in_Domain.mRID`.
+    pub coding_schemes: HashMap<String, String>,
+    /// `Reason` blocks of the series, e.g. flagging partial data.
+    pub reasons: Vec<Reason>,
+    /// Declared `timeInterval` of each period, in document order.
+    pub periods: Vec<Period>,
+    pub metadata: HashMap<String, Data>,
+    pub data: HashMap<String, Vec<Data>>,
+}
+
+impl TimeSeries {
+    fn set_field(&mut self, element: &str, text: &str) {
+        match element {
+            "inBiddingZone_Domain.mRID" => self.energy_flow = Some("generation".to_string()),
+            "outBiddingZone_Domain.mRID" => self.energy_flow = Some("consumption".to_string()),
+            _ => {}
+        }
+        let field = match element {
+            "mRID" => &mut self.mrid,
+            "businessType" => &mut self.business_type,
+            "in_Domain.mRID" | "inBiddingZone_Domain.mRID" => &mut self.in_domain,
+            "out_Domain.mRID" | "outBiddingZone_Domain.mRID" => &mut self.out_domain,
+            "currency_Unit.name" => &mut self.currency,
+            "price_Measure_Unit.name" | "quantity_Measure_Unit.name" => &mut self.measure_unit,
+            "curveType" => &mut self.curve_type,
+            "psrType" | "mktPSRType.psrType" => &mut self.psr_type,
+            "flowDirection.direction" => &mut self.flow_direction,
+            "auction.type" => &mut self.auction_type,
+            "contract_MarketAgreement.type" => &mut self.contract_agreement_type,
+            _ => return,
+        };
+        *field = Some(text.to_string());
+    }
+
+    fn push_point(&mut self, timestamp: Timestamp, values: &HashMap<String, Data>, resolution: &str) {
+        self.push_value("timestamp", Data::Timestamp(timestamp));
+        for (k, v) in values.iter() {
+            self.push_value(k, v.clone());
+        }
+        self.push_value("resolution", Data::String(resolution.to_string()));
+    }
+
+    /// Pushes to the `key` column, only allocating the key for a new column.
+    fn push_value(&mut self, key: &str, value: Data) {
+        match self.data.get_mut(key) {
+            Some(column) => column.push(value),
+            None => {
+                self.data.insert(key.to_string(), vec![value]);
+            }
+        }
+    }
+
+    /// Sorts the points by timestamp, keeping only the last point of each resolution and timestamp.
+    ///
+    /// Timestamps may be as converted by `ParseOptions::output_tz` or `ParseOptions::iso_timestamps`. Fails on any
+    /// other value rather than dropping its point. Without a `resolution` column, points are keyed on their timestamp
+    /// alone.
+    fn sort_points(&mut self) -> Result<(), DenkiError> {
+        let Some(timestamps) = self.data.get("timestamp") else {
+            return Ok(());
+        };
+        let resolutions = self.data.get("resolution");
+        let mut last_indices: HashMap<(Timestamp, Option<&str>), usize> = HashMap::new();
+        for (index, timestamp) in timestamps.iter().enumerate() {
+            let timestamp = match timestamp {
+                Data::Timestamp(timestamp) => *timestamp,
+                Data::Zoned(zoned) => zoned.timestamp(),
+                Data::String(text) => text
+                    .parse()
+                    .map_err(|_| DenkiError::Invalid(format!("cannot sort points on timestamp {text}")))?,
+                other => {
+                    return Err(DenkiError::Invalid(format!(
+                        "cannot sort points on timestamp {other:?}"
+                    )))
                 }
-                if name.local_name == period_name {
-                    current_period_start = None;
-                    current_period_resolution = None;
-                } else if name.local_name == "Point" {
-                    current_position = None;
-                    current_label_values = HashMap::new();
+            };
+            let resolution = match resolutions.and_then(|resolutions| resolutions.get(index)) {
+                Some(Data::String(resolution)) => Some(resolution.as_str()),
+                _ => None,
+            };
+            last_indices.insert((timestamp, resolution), index);
+        }
+        let mut indices: Vec<(Timestamp, usize)> = last_indices
+            .into_iter()
+            .map(|((timestamp, _), index)| (timestamp, index))
+            .collect();
+        indices.sort();
+
+        for values in self.data.values_mut() {
+            *values = indices
+                .iter()
+                .filter_map(|(_, index)| values.get(*index).cloned())
+                .collect();
+        }
+        Ok(())
+    }
+}
+
+/// The `timeInterval` of a period.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Period {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+/// Header of a document.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentMetadata {
+    pub mrid: Option<String>,
+    pub created_datetime: Option<Timestamp>,
+    /// `process.processType`, e.g. `A01` for day-ahead forecasts or `A16` for realised values.
+    pub process_type: Option<String>,
+    /// `codingScheme` attribute of the header identifiers, e.g. `A01` (EIC) for `sender_MarketParticipant.mRID`.
+    pub coding_schemes: HashMap<String, String>,
+}
+
+/// Counts of what a document parse went through.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseStats {
+    pub series: usize,
+    pub periods: usize,
+    /// Parsed points, including gap-filled ones.
+    pub points: usize,
+    /// Points skipped in lenient mode.
+    pub skipped: usize,
+}
+
+/// A parsed document, holding its `TimeSeries` in document order.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Document {
+    pub metadata: DocumentMetadata,
+    pub series: Vec<TimeSeries>,
+    /// Points and header fields skipped in lenient mode, see `ParseOptions::lenient`.
+    pub warnings: Vec<String>,
+    pub stats: ParseStats,
+}
+
+/// Quantities of a load document, along with the process they come from.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct Load {
+    pub process_type: Option<String>,
+    pub timestamp: Vec<Timestamp>,
+    pub quantity: Vec<i64>,
+}
+
+/// Weekly stored energy of a reservoir filling document (`A72`), along with its bidding zone and unit.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct Reservoir {
+    pub domain: Option<String>,
+    pub measure_unit: Option<String>,
+    pub timestamp: Vec<Timestamp>,
+    pub stored_energy: Vec<f64>,
+}
+
+/// Quantities of a wind and solar forecast document, per `psrType`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct GenerationForecast {
+    pub process_type: Option<String>,
+    /// Columns of each `psrType`, on its own timestamps.
+    pub series: HashMap<String, Columns>,
+    /// Union of the timestamps of every `psrType`, only filled when aligning.
+    pub timestamp: Vec<Timestamp>,
+    /// Values of each `psrType` on `timestamp`, `Data::Null` where a type lacks a point. Only filled when aligning.
+    pub aligned: HashMap<String, Vec<Data>>,
+}
+
+/// Prices of an imbalance price document, per flow direction.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct ImbalancePrices {
+    pub currency: Option<String>,
+    pub measure_unit: Option<String>,
+    /// Prices keyed by `flowDirection.direction`, `A01` for up and `A02` for down.
+    pub directions: HashMap<String, Columns>,
+}
+
+/// Parallel timestamp and value columns of a single label.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct Columns {
+    pub timestamp: Vec<Timestamp>,
+    pub value: Vec<f64>,
+}
+
+/// Flow of a cross-border document, e.g. physical flows (`A11`) or scheduled exchanges (`A09`), from `out_domain` to
+/// `in_domain`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct Flow {
+    pub out_domain: Option<String>,
+    pub in_domain: Option<String>,
+    pub timestamp: Vec<Timestamp>,
+    pub value: Vec<f64>,
+}
+
+/// Transfer capacity of a net transfer capacity document, e.g. forecasted (`A61`) or offered (`A31`), from `out_domain`
+/// to `in_domain` for the market of `contract_agreement_type` (e.g. `A01` for day-ahead, `A07` for intraday).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct TransferCapacity {
+    pub out_domain: Option<String>,
+    pub in_domain: Option<String>,
+    pub contract_agreement_type: Option<String>,
+    pub timestamp: Vec<Timestamp>,
+    pub value: Vec<f64>,
+}
+
+/// Activated balancing energy of a balancing document (`A83`/`A84`) in one `flow_direction` (`A01` for up, `A02` for
+/// down) of a reserve `business_type` (e.g. `A96` for aFRR or `A97` for mFRR).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct ActivatedEnergy {
+    pub flow_direction: Option<String>,
+    pub business_type: Option<String>,
+    pub measure_unit: Option<String>,
+    pub timestamp: Vec<Timestamp>,
+    pub value: Vec<f64>,
+}
+
+/// Flows of a cross-border document.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct Flows {
+    /// One flow per direction, in document order.
+    pub directions: Vec<Flow>,
+    /// For each pair of domains with flows in both directions, the flow of the first direction minus the opposite
+    /// one. Timestamps missing in one direction count as a zero flow.
+    pub net: Vec<Flow>,
+}
+
+/// The `Reason` given by ENTSO-E, e.g. in an `Acknowledgement_MarketDocument`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reason {
+    pub code: Option<String>,
+    pub text: Option<String>,
+}
+
+/// An interval of an unavailability `TimeSeries`, i.e. of an `Unavailability_MarketDocument` (`A77`, `A78`, `A80`).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", derive(IntoPyObject))]
+pub struct Outage {
+    pub mrid: Option<String>,
+    /// `A53` for a planned maintenance, `A54` for a forced unavailability.
+    pub business_type: Option<String>,
+    /// mRID of the unavailable production unit or transmission asset.
+    pub asset_mrid: Option<String>,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Capacity available during the interval, as given by the `Available_Period` point.
+    pub quantity: f64,
+    /// Reason of the `TimeSeries`, else of the document.
+    pub reason: Reason,
+}
+
+/// A malformed document, located at its 1-based `line` and `column`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: u64,
+    pub column: u64,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Failures told apart by their kind, e.g. to retry a rate-limited request but not a malformed document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DenkiError {
+    Parse(ParseError),
+    /// An unsuccessful HTTP status, other than those of `Auth` and `RateLimited`.
+    Http(u16),
+    /// The security token was rejected, with a 401 status.
+    Auth,
+    /// Too many requests, with a 429 status.
+    RateLimited,
+    /// A document the parsers cannot handle, e.g. of an unknown type for `parse_auto`.
+    Unsupported(String),
+    /// A well-formed document holding invalid values, e.g. a non-numeric position or an unknown resolution.
+    Invalid(String),
+    /// An `Acknowledgement_MarketDocument` in place of data, e.g. for a query matching no data.
+    Acknowledgement(Reason),
+    /// A document that could not be read, e.g. from a missing file.
+    Io(String),
+}
+
+impl DenkiError {
+    /// Error of an HTTP response status, `None` when successful.
+    pub fn from_status(status: u16) -> Option<Self> {
+        match status {
+            200..=399 => None,
+            401 => Some(Self::Auth),
+            429 => Some(Self::RateLimited),
+            _ => Some(Self::Http(status)),
+        }
+    }
+}
+
+impl std::fmt::Display for DenkiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::Http(status) => write!(f, "ENTSO-E responded with status {status}"),
+            Self::Auth => write!(f, "ENTSO-E rejected the security token"),
+            Self::RateLimited => write!(f, "ENTSO-E rate limit exceeded"),
+            Self::Unsupported(message) | Self::Invalid(message) | Self::Io(message) => write!(f, "{message}"),
+            Self::Acknowledgement(reason) => write!(
+                f,
+                "ENTSO-E error {}: {}",
+                reason.code.as_deref().unwrap_or_default(),
+                reason.text.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DenkiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for DenkiError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<std::io::Error> for DenkiError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+/// Arithmetic out of the range of timestamps, e.g. of a corrupt position or period start.
+impl From<jiff::Error> for DenkiError {
+    fn from(e: jiff::Error) -> Self {
+        Self::Invalid(e.to_string())
+    }
+}
+
+/// Downcasts the error of an `anyhow`-based caller, e.g. of a parser of its own built on these.
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for DenkiError {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast::<DenkiError>() {
+            Ok(e) => e,
+            Err(e) => match e.downcast::<ParseError>() {
+                Ok(e) => Self::Parse(e),
+                Err(e) => Self::Invalid(e.to_string()),
+            },
+        }
+    }
+}
+
+/// Fails on an HTML page, e.g. the error page of a misbehaving gateway served with a 200 status, which would otherwise
+/// fail further on as malformed XML.
+fn reject_html(head: &[u8]) -> Result<(), ParseError> {
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let lowercase = text.to_ascii_lowercase();
+    if !lowercase.starts_with("<!doctype html") && !lowercase.starts_with("<html") {
+        return Ok(());
+    }
+    let first_line = text.lines().next().unwrap_or_default();
+    Err(ParseError {
+        line: 1,
+        column: 1,
+        message: format!("response is not an ENTSO-E XML document but an HTML page: {first_line}"),
+    })
+}
+
+/// Name of an element, along with the namespace it resolves to.
+#[derive(Clone, Copy, Debug)]
+struct Name<'a> {
+    namespace: Option<&'a str>,
+    local_name: &'a str,
+}
+
+/// Markup read out of a document, the text in between being appended to a buffer of the caller.
+enum Markup<'a> {
+    /// An opening element, along with its `codingScheme` attribute, e.g. `A01` for an EIC code.
+    Start {
+        name: Name<'a>,
+        coding_scheme: Option<String>,
+    },
+    End(Name<'a>),
+    Eof,
+}
+
+/// Source of the markup of a document.
+trait MarkupReader {
+    /// Reads on until the next markup, appending the text before it to `text` unless it only holds whitespace, e.g.
+    /// the indentation between two elements.
+    fn next_markup(&mut self, text: &mut String) -> Result<Markup<'_>, ParseError>;
+}
+
+/// Local names of the open elements, the innermost last, reusing the allocation of each level from one element to the
+/// next.
+#[derive(Default)]
+struct Elements {
+    names: Vec<String>,
+    len: usize,
+}
+
+impl Elements {
+    fn push(&mut self, name: &str) {
+        match self.names.get_mut(self.len) {
+            Some(level) => {
+                level.clear();
+                level.push_str(name);
+            }
+            None => self.names.push(name.to_string()),
+        }
+        self.len += 1;
+    }
+
+    fn pop(&mut self) {
+        self.len = self.len.saturating_sub(1);
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Local name of the element holding the innermost one.
+    fn parent(&self) -> Option<&str> {
+        self.len.checked_sub(2).map(|i| self.names[i].as_str())
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.names[..self.len].iter().any(|level| level == name)
+    }
+}
+
+/// Counts the lines read out of `inner`, to locate an error at the point where reading stopped.
+struct LineCounter<R> {
+    inner: R,
+    line: u64,
+    column: u64,
+}
+
+impl<R: BufRead> Read for LineCounter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.fill_buf()?.read(buf)?;
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for LineCounter<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        // The data consumed is still buffered, so that filling the buffer again does not read on.
+        if let Ok(buffer) = self.inner.fill_buf() {
+            let consumed = &buffer[..amount.min(buffer.len())];
+            match consumed.iter().rposition(|byte| *byte == b'\n') {
+                Some(last_newline) => {
+                    self.line += consumed.iter().filter(|byte| **byte == b'\n').count() as u64;
+                    self.column = (consumed.len() - last_newline - 1) as u64;
                 }
+                None => self.column += consumed.len() as u64,
             }
-            Ok(XmlEvent::Characters(text)) => {
-                if current_element == Some("start".to_string()) {
-                    current_period_start = Some(text);
-                } else if current_element == Some("resolution".to_string()) {
-                    current_period_resolution = Some(text);
-                } else if current_element == Some("position".to_string()) {
-                    current_position = Some(text.parse()?);
-                } else {
-                    for label in &labels {
-                        if current_element == Some(label.to_string()) {
-                            if let Ok(current_label_value) = text.parse::<isize>() {
-                                current_label_values
-                                    .entry(label.to_string())
-                                    .insert_entry(Data::ISize(current_label_value));
-                            } else if let Ok(current_label_value) = text.parse::<f64>() {
-                                current_label_values
-                                    .entry(label.to_string())
-                                    .insert_entry(Data::F64(current_label_value));
-                            } else {
-                                current_label_values
-                                    .entry(label.to_string())
-                                    .insert_entry(Data::String(text.to_string()));
-                            }
-                        }
-                    }
-                    for metadata_element in &metadata {
-                        if current_element == Some(metadata_element.to_string()) {
-                            current_metadata_values
-                                .entry(metadata_element.to_string())
-                                .insert_entry(Data::String(text.to_string()));
-                        }
-                    }
+        }
+        self.inner.consume(amount);
+    }
+}
+
+/// Markup of a document read with `quick-xml`, decoded according to its XML declaration (e.g. UTF-8 or ISO-8859-1).
+///
+/// The buffer of the events and the name of the last element are reused from one event to the next.
+struct XmlReader<R: BufRead> {
+    reader: NsReader<LineCounter<DecodingReader<R>>>,
+    buffer: Vec<u8>,
+    namespace: Option<String>,
+    local_name: String,
+    depth: usize,
+    /// Whether an event was read, before which only the XML declaration may come.
+    started: bool,
+    /// Whether the root element was opened, a document without one being malformed.
+    root: bool,
+}
+
+/// What `XmlReader::next_markup` makes of one event.
+enum Step {
+    Start(Option<String>),
+    End,
+    /// Text appended to the caller's buffer, or an event of no interest, e.g. a comment.
+    Next,
+    Eof,
+}
+
+impl<R: BufRead> XmlReader<R> {
+    fn new(mut reader: R) -> Result<Self, DenkiError> {
+        let head = reader.fill_buf()?;
+        // The encoding is set before the first read, the declaration itself being ASCII.
+        let encoding = match Reader::from_reader(head).read_event_into(&mut Vec::new()) {
+            Ok(Event::Decl(declaration)) => declaration.encoder(),
+            _ => None,
+        };
+        let mut reader = DecodingReader::new(reader);
+        if let Some(encoding) = encoding {
+            reader.set_encoding(encoding);
+        }
+        let mut reader = NsReader::from_reader(LineCounter {
+            inner: reader,
+            line: 0,
+            column: 0,
+        });
+        reader.config_mut().expand_empty_elements = true;
+        Ok(Self {
+            reader,
+            buffer: Vec::new(),
+            namespace: None,
+            local_name: String::new(),
+            depth: 0,
+            started: false,
+            root: false,
+        })
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> ParseError {
+        let lines = self.reader.get_ref();
+        ParseError {
+            line: lines.line + 1,
+            column: lines.column + 1,
+            message: message.to_string(),
+        }
+    }
+
+    /// Keeps the name of the element of the last event, reusing the allocation of the previous one.
+    fn set_name(namespace: &mut Option<String>, local_name: &mut String, resolved: ResolveResult, name: &str) {
+        match (namespace.as_mut(), resolved) {
+            (Some(namespace), ResolveResult::Bound(resolved)) => {
+                namespace.clear();
+                namespace.push_str(resolved.as_ref());
+            }
+            (None, ResolveResult::Bound(resolved)) => *namespace = Some(resolved.as_ref().to_string()),
+            _ => *namespace = None,
+        }
+        local_name.clear();
+        local_name.push_str(name);
+    }
+}
+
+/// Reader of a time series document, failing on an HTML page rather than on its first malformed markup.
+fn document_reader<R: Read>(reader: R) -> Result<XmlReader<BufReader<R>>, DenkiError> {
+    let mut reader = BufReader::new(reader);
+    reject_html(reader.fill_buf()?)?;
+    XmlReader::new(reader)
+}
+
+/// Whether `text` only holds XML whitespace, e.g. the indentation between two elements.
+fn is_whitespace(text: &str) -> bool {
+    text.bytes().all(|byte| matches!(byte, b' ' | b'\t' | b'\r' | b'\n'))
+}
+
+/// Value of the `codingScheme` attribute of `element`, compared case-insensitively.
+fn coding_scheme(element: &BytesStart) -> Result<Option<String>, quick_xml::Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        if attribute.key.local_name().as_ref().eq_ignore_ascii_case("codingScheme") {
+            return Ok(Some(attribute.normalized_value(XmlVersion::Implicit1_0)?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// Text of an entity or character reference, e.g. `&` of `&amp;`.
+fn resolve_reference(reference: &BytesRef) -> Result<char, String> {
+    if let Some(character) = reference.resolve_char_ref().map_err(|e| e.to_string())? {
+        return Ok(character);
+    }
+    match &**reference {
+        "lt" => Ok('<'),
+        "gt" => Ok('>'),
+        "amp" => Ok('&'),
+        "apos" => Ok('\''),
+        "quot" => Ok('"'),
+        name => Err(format!("unknown entity &{name};")),
+    }
+}
+
+impl<R: BufRead> MarkupReader for XmlReader<R> {
+    fn next_markup(&mut self, text: &mut String) -> Result<Markup<'_>, ParseError> {
+        let text_start = text.len();
+        let step = loop {
+            self.buffer.clear();
+            let step = match self.reader.read_resolved_event_into(&mut self.buffer) {
+                Ok((resolved, Event::Start(element))) => {
+                    Self::set_name(
+                        &mut self.namespace,
+                        &mut self.local_name,
+                        resolved,
+                        element.local_name().as_ref(),
+                    );
+                    coding_scheme(&element).map(Step::Start).map_err(|e| e.to_string())
+                }
+                Ok((resolved, Event::End(element))) => {
+                    Self::set_name(
+                        &mut self.namespace,
+                        &mut self.local_name,
+                        resolved,
+                        element.local_name().as_ref(),
+                    );
+                    Ok(Step::End)
+                }
+                Ok((_, Event::Text(characters))) if self.depth == 0 && !is_whitespace(&characters) => {
+                    Err("unexpected text outside of the root element".to_string())
+                }
+                Ok((_, Event::Text(characters))) => {
+                    text.push_str(&characters.xml10_content());
+                    Ok(Step::Next)
+                }
+                Ok((_, Event::GeneralRef(_))) if self.depth == 0 => {
+                    Err("unexpected reference outside of the root element".to_string())
+                }
+                Ok((_, Event::GeneralRef(reference))) => resolve_reference(&reference).map(|character| {
+                    text.push(character);
+                    Step::Next
+                }),
+                Ok((_, Event::Decl(_))) if self.started => {
+                    Err("the XML declaration may only open the document".to_string())
                 }
+                Ok((_, Event::Eof)) => Ok(Step::Eof),
+                Ok(_) => Ok(Step::Next),
+                Err(e) => Err(e.to_string()),
+            };
+            self.started = true;
+            match step.map_err(|message| self.error(message))? {
+                step @ (Step::Start(_) | Step::End) => break step,
+                Step::Next => {}
+                Step::Eof if self.depth > 0 => {
+                    return Err(self.error("unexpected end of the document inside an element"))
+                }
+                Step::Eof if !self.root => return Err(self.error("no root element found")),
+                Step::Eof => return Ok(Markup::Eof),
             }
-            Ok(XmlEvent::EndElement { name }) => {
-                if let (Some(start), Some(resolution), Some(position)) =
-                    (&current_period_start, &current_period_resolution, &current_position)
-                {
-                    if name.local_name == "Point" {
-                        let start_iso = if start.ends_with("Z") {
-                            start.replace("Z", ":00Z")
-                        } else {
-                            start.clone() + ":00"
-                        };
-                        let start = start_iso.parse::<Timestamp>()?;
-                        let delta = RESOLUTIONS
-                            .get(resolution.as_str())
-                            .ok_or(anyhow!("Resolution not found"))?;
-                        let timestamp = start
-                            .to_zoned(TimeZone::UTC)
-                            .checked_add(*delta * (position - 1))?
-                            .timestamp();
-                        data.entry("timestamp".to_string())
-                            .or_default()
-                            .push(Data::Timestamp(timestamp));
-                        for (k, v) in current_label_values.iter() {
-                            data.entry(k.to_string()).or_default().push(v.clone());
-                        }
-                        for (k, v) in current_metadata_values.iter() {
-                            data.entry(k.to_string()).or_default().push(v.clone());
-                        }
-                        data.entry("resolution".to_string())
-                            .or_default()
-                            .push(Data::String(resolution.clone()));
+        };
+        if is_whitespace(&text[text_start..]) {
+            text.truncate(text_start);
+        }
+        let name = Name {
+            namespace: self.namespace.as_deref(),
+            local_name: &self.local_name,
+        };
+        if let Step::Start(coding_scheme) = step {
+            self.depth += 1;
+            self.root = true;
+            Ok(Markup::Start { name, coding_scheme })
+        } else {
+            self.depth -= 1;
+            Ok(Markup::End(name))
+        }
+    }
+}
+
+/// Names of the elements making up a period, for schemas departing from the `Publication_MarketDocument` ones.
+///
+/// The values are read from the `labels` elements of each point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementNames {
+    pub point: String,
+    pub position: String,
+    pub resolution: String,
+}
+
+impl Default for ElementNames {
+    fn default() -> Self {
+        Self {
+            point: "Point".to_string(),
+            position: "position".to_string(),
+            resolution: "resolution".to_string(),
+        }
+    }
+}
+
+/// Options tuning how a document is parsed.
+#[derive(Clone, Debug)]
+pub struct ParseOptions {
+    /// Time zone in which points are stepped, so that calendar resolutions (`P1D`, `P1M`, `P1Y`) follow its
+    /// daylight saving time transitions. Sub-daily resolutions are absolute durations and are not affected.
+    pub tz: TimeZone,
+    /// Sorts each `TimeSeries` points by timestamp and drops duplicated timestamps, e.g. from overlapping periods,
+    /// keeping the last one. Only applies to `parse_timeseries_document`, the generic output keeps document order.
+    pub sort_points: bool,
+    /// Inserts `Data::Null` values at the positions missing from a period of an `A01` (fixed size blocks) curve, from
+    /// the first to the last position of its `timeInterval`, so that points stay aligned to the period grid.
+    pub fill_missing: bool,
+    /// Fails when a period does not hold the `(end - start) / resolution` points of its `timeInterval`, or, unless
+    /// `fill_missing` is set, when its positions skip one, naming the first missing position and its timestamp. `A03`
+    /// curves are not checked as they only carry the points where the value changes.
+    pub validate_points: bool,
+    /// Returns the `timestamp` column as ISO 8601 strings (e.g. `2023-12-31T23:00:00Z`) instead of timestamps, which
+    /// Python receives as UTC `datetime`s.
+    pub iso_timestamps: bool,
+    /// Converts the `timestamp` column to `Data::Zoned` local times of this time zone, e.g. `Europe/Paris` for market
+    /// time, which Python receives as timezone-aware `datetime`s. Applied before `iso_timestamps`. A `utc_offset` column
+    /// holds the offset of each point in seconds, telling apart the local times repeated when daylight saving time ends.
+    pub output_tz: Option<TimeZone>,
+    /// Skips the `Point`s whose position or label values are not numbers, and an invalid `createdDateTime` of the
+    /// header, recording a warning in `Document::warnings`, instead of failing or keeping the values as strings.
+    pub lenient: bool,
+    /// Reads a `,` in label values as the decimal separator, e.g. `104,98`, for documents re-exported with a
+    /// locale using it. Positions are integers and are not affected.
+    pub decimal_comma: bool,
+    /// Number of the first position of a period: 1 as in the ENTSO-E schemas, or 0 for documents numbering positions
+    /// from 0. Positions are read relative to it, the first one landing on the period start.
+    pub position_base: i64,
+    /// Adds a `position` column holding the `<position>` of each point in the document, to cross-check the parsed
+    /// points against it. Gap-filled points hold `Data::Null`.
+    pub keep_positions: bool,
+    /// Names of the point, position and resolution elements.
+    pub element_names: ElementNames,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            tz: TimeZone::UTC,
+            sort_points: true,
+            fill_missing: false,
+            validate_points: false,
+            iso_timestamps: false,
+            output_tz: None,
+            lenient: false,
+            decimal_comma: false,
+            position_base: 1,
+            keep_positions: false,
+            element_names: ElementNames::default(),
+        }
+    }
+}
+
+/// Parses an ISO-8601 duration (`PnYnMnDTnHnMnS`) such as `PT15M` or `P1Y`, failing on zero or negative ones, which
+/// would never step forward.
+fn resolution_to_timedelta(resolution: &str) -> Result<Span, DenkiError> {
+    let span = SPAN_PARSER
+        .parse_span(resolution)
+        .map_err(|_| DenkiError::Invalid(format!("unsupported resolution: {resolution}")))?;
+    if span.signum() <= 0 {
+        return Err(DenkiError::Invalid(format!(
+            "resolution must be positive: {resolution}"
+        )));
+    }
+    Ok(span)
+}
+
+/// Parses a `timeInterval` bound, e.g. `2023-12-31T23:00Z`, `2023-12-31T23:00:00Z` or `2023-12-31T23:00+01:00`.
+///
+/// Seconds are optional and bounds without a zone designator are taken as UTC.
+fn interval_timestamp(text: &str) -> Result<Timestamp, DenkiError> {
+    let text = text.trim();
+    text.parse::<Timestamp>()
+        .or_else(|_| format!("{text}Z").parse::<Timestamp>())
+        .map_err(|e| DenkiError::Invalid(format!("invalid timestamp {text:?}: {e}")))
+}
+
+/// Computes the timestamp of the point at `position` (1-based) in a period.
+fn point_timestamp(start: &str, resolution: &str, position: i64, tz: &TimeZone) -> Result<Timestamp, DenkiError> {
+    let start = interval_timestamp(start)?;
+    let delta = resolution_to_timedelta(resolution)?;
+    grid_timestamp(start, delta, position, tz)
+}
+
+/// Same as `point_timestamp`, with an already parsed `start` and resolution `delta`.
+fn grid_timestamp(start: Timestamp, delta: Span, position: i64, tz: &TimeZone) -> Result<Timestamp, DenkiError> {
+    let delta = delta.checked_mul(position - 1)?;
+    // Only calendar units depend on the time zone, sub-daily resolutions are added to the timestamp directly.
+    let timestamp =
+        if delta.get_years() == 0 && delta.get_months() == 0 && delta.get_weeks() == 0 && delta.get_days() == 0 {
+            start.checked_add(delta)?
+        } else {
+            start.to_zoned(tz.clone()).checked_add(delta)?.timestamp()
+        };
+    Ok(timestamp)
+}
+
+/// Matches an element against a requested label, either a bare local name (`price.amount`) matching any namespace,
+/// or a qualified name (`{urn:...}price.amount`) matching only that namespace.
+fn matches_name(label: &str, name: Name) -> bool {
+    match label.strip_prefix('{').and_then(|label| label.split_once('}')) {
+        Some((namespace, local_name)) => name.local_name == local_name && name.namespace == Some(namespace),
+        None => name.local_name == label,
+    }
+}
+
+/// Whether `local_name` is the period element `period_name`, compared case-insensitively and without the namespace
+/// prefix of `period_name`, so that `period` and `ns:Period` both match `Period`.
+fn is_period(period_name: &str, local_name: &str) -> bool {
+    let period_name = period_name.rsplit_once(':').map_or(period_name, |(_, name)| name);
+    local_name.eq_ignore_ascii_case(period_name)
+}
+
+/// Parsed start and resolution of a period, parsed once on its first point or at its end.
+#[derive(Clone, Copy)]
+struct PeriodGrid {
+    start: Timestamp,
+    delta: Span,
+}
+
+impl PeriodGrid {
+    fn new(start: &str, resolution: &str) -> Result<Self, DenkiError> {
+        Ok(Self {
+            start: interval_timestamp(start)?,
+            delta: resolution_to_timedelta(resolution)?,
+        })
+    }
+
+    fn timestamp(&self, position: i64, tz: &TimeZone) -> Result<Timestamp, DenkiError> {
+        grid_timestamp(self.start, self.delta, position, tz)
+    }
+
+    /// Number of points of the period, stepping from its start until `end`.
+    ///
+    /// Fails beyond `MAX_PERIOD_POINTS`, e.g. of a second resolution over years, rather than stepping on and on.
+    fn point_count(&self, end: &str, tz: &TimeZone) -> Result<i64, DenkiError> {
+        let end = interval_timestamp(end)?;
+        let mut count = 0;
+        while self.timestamp(count + 1, tz)? < end {
+            count += 1;
+            if count > MAX_PERIOD_POINTS {
+                return Err(DenkiError::Invalid(format!(
+                    "period from {} holds more than {MAX_PERIOD_POINTS} points at {} resolution",
+                    self.start, self.delta
+                )));
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// `Data::Null` values of `labels`, filling a missing position.
+fn null_values(labels: &[&str]) -> HashMap<String, Data> {
+    labels.iter().map(|label| (label.to_string(), Data::Null)).collect()
+}
+
+/// Reads the first `Reason` out of the remaining markup of an `Acknowledgement_MarketDocument`.
+fn read_reason(reader: &mut impl MarkupReader) -> Result<Reason, DenkiError> {
+    let mut reason: Option<Reason> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.next_markup(&mut text)? {
+            Markup::Start { name, .. } => {
+                if name.local_name == "Reason" && reason.is_none() {
+                    reason = Some(Reason::default());
+                }
+                text.clear();
+            }
+            Markup::End(name) => {
+                if let Some(reason) = reason.as_mut() {
+                    match name.local_name {
+                        "code" if reason.code.is_none() => reason.code = Some(text.clone()),
+                        "text" if reason.text.is_none() => reason.text = Some(text.clone()),
+                        _ => {}
                     }
                 }
+                text.clear();
             }
-            Err(e) => return Err(e.into()),
-            _ => {}
+            Markup::Eof => break,
         }
     }
 
-    Ok(data)
+    Ok(reason.unwrap_or_default())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{parse_timeseries_generic, Data};
+/// Returns the `Reason` of an `Acknowledgement_MarketDocument`, or `None` when the document is not an acknowledgement.
+pub fn parse_acknowledgement(xml_text: &str) -> Result<Option<Reason>, DenkiError> {
+    let mut reader = XmlReader::new(xml_text.as_bytes())?;
+    let mut text = String::new();
 
-    #[test]
-    fn test_parse_timeseries_generic_day_ahead_price() {
-        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
-        <publication_marketdocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
-        <mRID>bf4445f7e6e04c849b7e0830b906fbde</mRID>
-        <revisionnumber>1</revisionnumber>
-        <type>A44</type>
-        <sender_marketparticipant.mRID codingscheme="A01">10X1001A1001A450</sender_marketparticipant.mRID>
+    match reader.next_markup(&mut text)? {
+        Markup::Start { name, .. } if name.local_name == "Acknowledgement_MarketDocument" => {
+            read_reason(&mut reader).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the `type` of a document, e.g. `A44` for prices, read from its header without parsing the series.
+pub fn document_type(xml_text: &str) -> Result<Option<String>, DenkiError> {
+    let mut reader = XmlReader::new(xml_text.as_bytes())?;
+    let mut depth = 0;
+    let mut in_type = false;
+    let mut text = String::new();
+
+    loop {
+        match reader.next_markup(&mut text)? {
+            Markup::Start { name, .. } => {
+                depth += 1;
+                if depth == 2 && name.local_name == "TimeSeries" {
+                    break;
+                }
+                in_type = depth == 2 && name.local_name.eq_ignore_ascii_case("type");
+                text.clear();
+            }
+            Markup::End(_) => {
+                if in_type {
+                    return Ok(Some(text));
+                }
+                depth -= 1;
+            }
+            Markup::Eof => break,
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses the series of a document, failing with a `DenkiError` telling apart the kind of failure, e.g. a malformed
+/// document from an invalid value.
+pub fn try_parse_timeseries_document(
+    xml_text: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<Document, DenkiError> {
+    parse_timeseries_reader(xml_text.as_bytes(), labels, metadata, period_name, options)
+}
+
+/// Same as `try_parse_timeseries_document` with the default options, failing with an `anyhow::Error`.
+#[cfg(feature = "anyhow")]
+pub fn parse_timeseries_document(
+    xml_text: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+) -> Result<Document, anyhow::Error> {
+    parse_timeseries_document_with_options(xml_text, labels, metadata, period_name, &ParseOptions::default())
+}
+
+/// Same as `try_parse_timeseries_document`, failing with an `anyhow::Error`.
+#[cfg(feature = "anyhow")]
+pub fn parse_timeseries_document_with_options(
+    xml_text: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<Document, anyhow::Error> {
+    Ok(try_parse_timeseries_document(
+        xml_text,
+        labels,
+        metadata,
+        period_name,
+        options,
+    )?)
+}
+
+/// Parses several documents concatenated in `xml_text`, e.g. by tooling appending `*_MarketDocument`s to one file, and
+/// merges their series in order. Documents are split on their XML declaration, the ones without a declaration being
+/// read as further root elements. The metadata is the one of the first document.
+pub fn parse_timeseries_multidoc(
+    xml_text: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<Document, DenkiError> {
+    let mut merged: Option<Document> = None;
+    let starts: Vec<usize> = xml_text.match_indices("<?xml ").map(|(start, _)| start).collect();
+    let bounds = std::iter::once(0).chain(starts.iter().copied().filter(|start| *start > 0));
+    let ends = bounds.clone().skip(1).chain(std::iter::once(xml_text.len()));
+
+    for (start, end) in bounds.zip(ends) {
+        let chunk = &xml_text[start..end];
+        if chunk.trim().is_empty() {
+            continue;
+        }
+        let document = try_parse_timeseries_document(chunk, labels.clone(), metadata.clone(), period_name, options)?;
+        match merged.as_mut() {
+            None => merged = Some(document),
+            Some(merged) => {
+                merged.series.extend(document.series);
+                merged.warnings.extend(document.warnings);
+                merged.stats.series += document.stats.series;
+                merged.stats.periods += document.stats.periods;
+                merged.stats.points += document.stats.points;
+                merged.stats.skipped += document.stats.skipped;
+            }
+        }
+    }
+    Ok(merged.unwrap_or_default())
+}
+
+/// Streams a document out of `reader`, decoding it according to its XML declaration (e.g. UTF-8 or ISO-8859-1).
+fn parse_timeseries_reader<R: Read>(
+    reader: R,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<Document, DenkiError> {
+    parse_timeseries_events(
+        document_reader(reader)?,
+        labels,
+        metadata,
+        period_name,
+        options,
+        |_, series, timestamp, values, resolution| {
+            series.push_point(timestamp, values, resolution);
+            ControlFlow::Continue(())
+        },
+    )
+}
+
+/// Same as `parse_timeseries_reader`, handing each point to `on_point` along with its `TimeSeries` and the index of
+/// that series in the document instead of pushing it. Parsing stops as soon as `on_point` breaks, returning the
+/// document parsed so far.
+fn parse_timeseries_events<M, F>(
+    mut reader: M,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+    mut on_point: F,
+) -> Result<Document, DenkiError>
+where
+    M: MarkupReader,
+    F: FnMut(usize, &mut TimeSeries, Timestamp, &HashMap<String, Data>, &str) -> ControlFlow<()>,
+{
+    let mut document = Document::default();
+    let mut current_series = TimeSeries::default();
+    let names = &options.element_names;
+    // Positions are handled 1-based, and reported as numbered in the document.
+    let document_position = |position: i64| position + options.position_base - 1;
+    // Hands `values` to `on_point` at each of `positions`, a parsed point or the positions filled before or after it,
+    // counting them in `period_points` and `stats`. Fails beyond `MAX_PERIOD_POINTS`, e.g. of a corrupt position,
+    // rather than filling on and on.
+    let mut push_points = |series: (usize, &mut TimeSeries),
+                           grid: PeriodGrid,
+                           positions: std::ops::Range<i64>,
+                           values: &HashMap<String, Data>,
+                           resolution: &str,
+                           period_points: &mut i64,
+                           stats: &mut ParseStats|
+     -> Result<ControlFlow<()>, DenkiError> {
+        if positions.end > MAX_PERIOD_POINTS + 1 {
+            return Err(DenkiError::Invalid(format!(
+                "positions {} to {} exceed the {MAX_PERIOD_POINTS} points a period may hold",
+                document_position(positions.start),
+                document_position(positions.end - 1)
+            )));
+        }
+        let (index, series) = series;
+        for position in positions {
+            let timestamp = grid.timestamp(position, &options.tz)?;
+            if on_point(index, series, timestamp, values, resolution).is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+            *period_points += 1;
+            stats.points += 1;
+        }
+        Ok(ControlFlow::Continue(()))
+    };
+
+    let mut current_period_start: Option<String> = None;
+    let mut current_period_end: Option<String> = None;
+    let mut current_period_resolution: Option<String> = None;
+    let mut current_period_points: i64 = 0;
+    // Parsed start and resolution of the current period, computed on its first point.
+    let mut current_grid: Option<PeriodGrid> = None;
+    // Number of points of the current period, computed once needed.
+    let mut current_period_count: Option<i64> = None;
+    let mut current_position: Option<i64> = None;
+    let mut current_label_values: HashMap<String, Data> = HashMap::new();
+    let mut current_point_error: Option<String> = None;
+    let mut current_coding_scheme: Option<String> = None;
+    let mut current_reason = Reason::default();
+    let mut last_point: Option<(i64, HashMap<String, Data>)> = None;
+    let mut elements = Elements::default();
+    // The text of an element may be split by entities or comments, so it is only parsed once the element ends.
+    let mut text = String::new();
+
+    loop {
+        match reader.next_markup(&mut text)? {
+            Markup::Start { name, coding_scheme } => {
+                if elements.is_empty() && name.local_name == "Acknowledgement_MarketDocument" {
+                    return Err(DenkiError::Acknowledgement(read_reason(&mut reader)?));
+                }
+                text.clear();
+                if name.local_name == "TimeSeries" {
+                    current_series = TimeSeries {
+                        process_type: document.metadata.process_type.clone(),
+                        ..Default::default()
+                    };
+                    last_point = None;
+                }
+                // A period opens with its `timeInterval`, so that its start and resolution never carry over from the
+                // previous period of the series.
+                if is_period(period_name, name.local_name) || name.local_name == "timeInterval" {
+                    current_period_start = None;
+                    current_period_end = None;
+                    current_period_resolution = None;
+                    current_period_points = 0;
+                    current_grid = None;
+                    current_period_count = None;
+                    last_point = None;
+                } else if name.local_name == names.point {
+                    current_position = None;
+                    current_label_values = HashMap::new();
+                    current_point_error = None;
+                }
+                if name.local_name.ends_with("mRID") {
+                    current_coding_scheme = coding_scheme;
+                }
+                elements.push(name.local_name);
+            }
+            Markup::End(name) => {
+                let element = name.local_name;
+                // Only the bounds of a period's `timeInterval` are read, not those of `period.timeInterval` in the
+                // document header.
+                let in_time_interval = elements.parent() == Some("timeInterval");
+                if in_time_interval && element == "start" {
+                    current_period_start = Some(text.clone());
+                    current_grid = None;
+                    current_period_count = None;
+                    last_point = None;
+                } else if in_time_interval && element == "end" {
+                    current_period_end = Some(text.clone());
+                } else if element == "timeInterval" {
+                    if let (Some(start), Some(end)) = (&current_period_start, &current_period_end) {
+                        current_series.periods.push(Period {
+                            start: interval_timestamp(start)?,
+                            end: interval_timestamp(end)?,
+                        });
+                    }
+                } else if element == names.resolution {
+                    current_period_resolution = Some(text.clone());
+                    current_grid = None;
+                    current_period_count = None;
+                } else if element == names.position {
+                    // Positions and values may be padded with whitespace or newlines.
+                    match text.trim().parse::<i64>() {
+                        Ok(position) => current_position = Some(position - options.position_base + 1),
+                        Err(_) if options.lenient => current_point_error = Some(format!("invalid position {text:?}")),
+                        Err(_) => return Err(DenkiError::Invalid(format!("invalid position {text:?}"))),
+                    }
+                } else if is_period(period_name, element) {
+                    let next_position = last_point.as_ref().map_or(1, |(last_position, _)| last_position + 1);
+                    let tail = match (current_series.curve_type.as_deref(), &last_point) {
+                        // The last point of an A03 curve holds until the end of the period.
+                        (Some("A03"), Some((_, last_values))) => Some(last_values.clone()),
+                        (Some("A01"), _) if options.fill_missing => Some(null_values(&labels)),
+                        _ => None,
+                    };
+                    let checked = options.validate_points && current_series.curve_type.as_deref() != Some("A03");
+                    let count = match (&current_period_start, &current_period_end, &current_period_resolution) {
+                        (Some(start), Some(end), Some(resolution)) if tail.is_some() || checked => {
+                            let grid = match current_grid {
+                                Some(grid) => grid,
+                                None => PeriodGrid::new(start, resolution)?,
+                            };
+                            let count = match current_period_count {
+                                Some(count) => count,
+                                None => grid.point_count(end, &options.tz)?,
+                            };
+                            Some((grid, count))
+                        }
+                        _ => None,
+                    };
+                    if let (Some(mut filled_values), Some((grid, count)), Some(resolution)) =
+                        (tail, count, &current_period_resolution)
+                    {
+                        if options.keep_positions {
+                            filled_values.insert("position".to_string(), Data::Null);
+                        }
+                        if push_points(
+                            (document.series.len(), &mut current_series),
+                            grid,
+                            next_position..count + 1,
+                            &filled_values,
+                            resolution,
+                            &mut current_period_points,
+                            &mut document.stats,
+                        )?
+                        .is_break()
+                        {
+                            return Ok(document);
+                        }
+                    }
+                    if let (true, Some(start), Some(end), Some(resolution), Some((_, expected))) = (
+                        checked,
+                        &current_period_start,
+                        &current_period_end,
+                        &current_period_resolution,
+                        count,
+                    ) {
+                        if current_period_points != expected {
+                            return Err(DenkiError::Invalid(format!(
+                                "period from {start} to {end} has {current_period_points} points, expected {expected} \
+                                 at {resolution} resolution"
+                            )));
+                        }
+                    }
+                    log::debug!(
+                        "period from {} to {} of TimeSeries {}: {current_period_points} points at {} resolution",
+                        current_period_start.as_deref().unwrap_or("?"),
+                        current_period_end.as_deref().unwrap_or("?"),
+                        document.series.len() + 1,
+                        current_period_resolution.as_deref().unwrap_or("?"),
+                    );
+                } else if element == "TimeSeries" {
+                    log::debug!(
+                        "TimeSeries {} ({}): {} periods",
+                        document.series.len() + 1,
+                        current_series.mrid.as_deref().unwrap_or("no mRID"),
+                        current_series.periods.len()
+                    );
+                    document.series.push(std::mem::take(&mut current_series));
+                } else if element == "Reason" {
+                    let reason = std::mem::take(&mut current_reason);
+                    if elements.parent() == Some("TimeSeries") {
+                        current_series.reasons.push(reason);
+                    }
+                } else if element == names.point {
+                    if let Some(error) = current_point_error.take() {
+                        let warning = format!(
+                            "skipped point {} of TimeSeries {}: {error}",
+                            current_position
+                                .map_or("?".to_string(), |position| document_position(position).to_string()),
+                            document.series.len() + 1
+                        );
+                        log::warn!("{warning}");
+                        document.warnings.push(warning);
+                        document.stats.skipped += 1;
+                    } else if let (Some(start), Some(resolution), Some(position)) =
+                        (&current_period_start, &current_period_resolution, &current_position)
+                    {
+                        // Missing labels are filled so that every column stays aligned with the timestamps.
+                        for label in labels.iter() {
+                            if !current_label_values.contains_key(*label) {
+                                current_label_values.insert(label.to_string(), Data::Null);
+                            }
+                        }
+                        let grid = match current_grid {
+                            Some(grid) => grid,
+                            None => *current_grid.insert(PeriodGrid::new(start, resolution)?),
+                        };
+                        let checked = options.validate_points
+                            && !options.fill_missing
+                            && current_series.curve_type.as_deref() != Some("A03");
+                        let expected = last_point.as_ref().map_or(1, |(last_position, _)| last_position + 1);
+                        if checked && *position > expected {
+                            let timestamp = grid.timestamp(expected, &options.tz)?;
+                            return Err(DenkiError::Invalid(format!(
+                                "missing position {} ({timestamp}) before position {} of TimeSeries {}",
+                                document_position(expected),
+                                document_position(*position),
+                                document.series.len() + 1
+                            )));
+                        }
+                        let filled_values = match (current_series.curve_type.as_deref(), &last_point) {
+                            // A03 curves only carry a point when the value changes: it holds until the next position.
+                            (Some("A03"), Some((_, last_values))) => Some(last_values.clone()),
+                            // Including the positions before the first point of the period.
+                            (Some("A01"), _) if options.fill_missing => Some(null_values(&labels)),
+                            _ => None,
+                        };
+                        if let Some(mut filled_values) = filled_values {
+                            // Filling up to a position beyond the end of the period would only add points outside it.
+                            if let Some(end) = &current_period_end {
+                                let count = match current_period_count {
+                                    Some(count) => count,
+                                    None => *current_period_count.insert(grid.point_count(end, &options.tz)?),
+                                };
+                                if *position > count {
+                                    return Err(DenkiError::Invalid(format!(
+                                        "position {} of TimeSeries {} is beyond the {count} points of its period from \
+                                         {start} to {end}",
+                                        document_position(*position),
+                                        document.series.len() + 1
+                                    )));
+                                }
+                            }
+                            if options.keep_positions {
+                                filled_values.insert("position".to_string(), Data::Null);
+                            }
+                            if push_points(
+                                (document.series.len(), &mut current_series),
+                                grid,
+                                expected..*position,
+                                &filled_values,
+                                resolution,
+                                &mut current_period_points,
+                                &mut document.stats,
+                            )?
+                            .is_break()
+                            {
+                                return Ok(document);
+                            }
+                        }
+                        if options.keep_positions {
+                            current_label_values
+                                .insert("position".to_string(), Data::I64(document_position(*position)));
+                        }
+                        if push_points(
+                            (document.series.len(), &mut current_series),
+                            grid,
+                            *position..*position + 1,
+                            &current_label_values,
+                            resolution,
+                            &mut current_period_points,
+                            &mut document.stats,
+                        )?
+                        .is_break()
+                        {
+                            return Ok(document);
+                        }
+                        last_point = Some((*position, std::mem::take(&mut current_label_values)));
+                    }
+                } else {
+                    // Header of the document, whose element names may be lowercased.
+                    if elements.len() == 2 && element.eq_ignore_ascii_case("mRID") {
+                        document.metadata.mrid = Some(text.clone());
+                    } else if elements.len() == 2 && element.eq_ignore_ascii_case("createdDateTime") {
+                        match text.trim().parse() {
+                            Ok(created_datetime) => document.metadata.created_datetime = Some(created_datetime),
+                            Err(e) if options.lenient => {
+                                let warning = format!("skipped createdDateTime {text:?}: {e}");
+                                log::warn!("{warning}");
+                                document.warnings.push(warning);
+                            }
+                            Err(e) => {
+                                return Err(DenkiError::Invalid(format!("invalid createdDateTime {text:?}: {e}")))
+                            }
+                        }
+                    } else if elements.len() == 2 && element == "process.processType" {
+                        document.metadata.process_type = Some(text.clone());
+                    }
+                    if elements.parent() == Some("Reason") {
+                        match element {
+                            "code" => current_reason.code = Some(text.clone()),
+                            "text" => current_reason.text = Some(text.clone()),
+                            _ => {}
+                        }
+                    }
+                    if let Some(coding_scheme) = current_coding_scheme.take() {
+                        if elements.len() == 2 {
+                            document
+                                .metadata
+                                .coding_schemes
+                                .insert(element.to_string(), coding_scheme);
+                        } else if elements.parent() == Some("TimeSeries") {
+                            current_series.coding_schemes.insert(element.to_string(), coding_scheme);
+                        }
+                    }
+                    // `psrType` is nested in a `MktPSRType` block of generation documents.
+                    if matches!(elements.parent(), Some("TimeSeries" | "MktPSRType")) {
+                        current_series.set_field(element, &text);
+                    }
+                    if let Some(label) = labels.iter().find(|label| matches_name(label, name)) {
+                        let value_text = text.trim();
+                        let normalized;
+                        let value_text = if options.decimal_comma {
+                            normalized = value_text.replace(',', ".");
+                            normalized.as_str()
+                        } else {
+                            value_text
+                        };
+                        let value = if let Ok(value) = value_text.parse::<i64>() {
+                            Data::I64(value)
+                        } else if let Ok(value) = value_text.parse::<f64>() {
+                            Data::F64(value)
+                        } else {
+                            if options.lenient {
+                                current_point_error = Some(format!("non-numeric {label} {text:?}"));
+                            }
+                            Data::String(text.clone())
+                        };
+                        current_label_values.insert(label.to_string(), value);
+                    }
+                    if let Some(metadata) = metadata.iter().find(|metadata| matches_name(metadata, name)) {
+                        current_series
+                            .metadata
+                            .insert(metadata.to_string(), Data::String(text.clone()));
+                    }
+                }
+                elements.pop();
+                text.clear();
+            }
+            Markup::Eof => break,
+        }
+    }
+
+    document.stats.series = document.series.len();
+    document.stats.periods = document.series.iter().map(|series| series.periods.len()).sum();
+    log::debug!(
+        "document {}: {} series, {} periods, {} points, {} skipped points",
+        document.metadata.mrid.as_deref().unwrap_or("without mRID"),
+        document.stats.series,
+        document.stats.periods,
+        document.stats.points,
+        document.stats.skipped
+    );
+
+    for series in document.series.iter_mut() {
+        if options.sort_points {
+            series.sort_points()?;
+        }
+        let mut offsets = vec![];
+        for timestamp in series.data.get_mut("timestamp").into_iter().flatten() {
+            if let (Some(tz), Data::Timestamp(t)) = (&options.output_tz, &timestamp) {
+                let zoned = t.to_zoned(tz.clone());
+                offsets.push(Data::I64(zoned.offset().seconds().into()));
+                *timestamp = Data::Zoned(zoned);
+            }
+            if options.iso_timestamps {
+                match timestamp {
+                    Data::Timestamp(t) => *timestamp = Data::String(t.to_string()),
+                    Data::Zoned(t) => *timestamp = Data::String(t.to_string()),
+                    _ => {}
+                }
+            }
+        }
+        if options.output_tz.is_some() {
+            series.data.insert("utc_offset".to_string(), offsets);
+        }
+    }
+
+    Ok(document)
+}
+
+pub fn parse_timeseries_generic(
+    xml_text: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    parse_timeseries_generic_with_options(xml_text, labels, metadata, period_name, &ParseOptions::default())
+}
+
+pub fn parse_timeseries_generic_with_options(
+    xml_text: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    parse_timeseries_generic_reader(
+        document_reader(xml_text.as_bytes())?,
+        labels,
+        metadata,
+        period_name,
+        options,
+    )
+}
+
+/// Same as `parse_timeseries_generic_with_options`, streaming the document from a file.
+pub fn parse_timeseries_from_path(
+    path: &str,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    let file = File::open(path).map_err(|e| DenkiError::Io(format!("failed to open {path}: {e}")))?;
+    parse_timeseries_generic_reader(document_reader(file)?, labels, metadata, period_name, options)
+}
+
+/// Same as `parse_timeseries_generic_with_options`, reading the document from raw bytes.
+pub fn parse_timeseries_from_bytes(
+    bytes: &[u8],
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    parse_timeseries_generic_reader(document_reader(bytes)?, labels, metadata, period_name, options)
+}
+
+/// Flattens the series of a document into columns. The `metadata` value of a point is the last one read before it in
+/// its series, e.g. an `imbalance_Price.category` changing from point to point, or else the last one of the series,
+/// e.g. of an element following the period, and `Data::Null` when the series has none.
+fn parse_timeseries_generic_reader(
+    reader: impl MarkupReader,
+    labels: Vec<&str>,
+    metadata: Vec<&str>,
+    period_name: &str,
+    options: &ParseOptions,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    let options = ParseOptions {
+        sort_points: false,
+        ..options.clone()
+    };
+    let names: Vec<String> = metadata.iter().map(|name| name.to_string()).collect();
+    let mut point_series = Vec::new();
+    let mut point_metadata: Vec<Vec<Option<Data>>> = vec![Vec::new(); names.len()];
+    let document = parse_timeseries_events(
+        reader,
+        labels,
+        metadata,
+        period_name,
+        &options,
+        |index, series, timestamp, values, resolution| {
+            series.push_point(timestamp, values, resolution);
+            point_series.push(index);
+            for (name, column) in names.iter().zip(point_metadata.iter_mut()) {
+                column.push(series.metadata.get(name).cloned());
+            }
+            ControlFlow::Continue(())
+        },
+    )?;
+    let mut data: HashMap<String, Vec<Data>> = HashMap::new();
+    let mut series_metadata = Vec::with_capacity(document.series.len());
+
+    for series in document.series {
+        for (k, v) in series.data {
+            data.entry(k).or_default().extend(v);
+        }
+        series_metadata.push(series.metadata);
+    }
+    for (name, column) in names.into_iter().zip(point_metadata) {
+        if column.iter().all(Option::is_none) && series_metadata.iter().all(|m| !m.contains_key(&name)) {
+            continue;
+        }
+        let column = column
+            .into_iter()
+            .zip(&point_series)
+            .map(|(value, index)| {
+                value
+                    .or_else(|| series_metadata[*index].get(&name).cloned())
+                    .unwrap_or(Data::Null)
+            })
+            .collect();
+        data.insert(name, column);
+    }
+
+    Ok(data)
+}
+
+/// Calls `callback` with the `(resolution, timestamp, value)` of each point as it is parsed, without accumulating the
+/// points. Parsing stops as soon as `callback` breaks.
+pub fn for_each_point<F>(xml_text: &str, label: &str, period_name: &str, mut callback: F) -> Result<(), DenkiError>
+where
+    F: FnMut(&str, Timestamp, &Data) -> ControlFlow<()>,
+{
+    let options = ParseOptions {
+        sort_points: false,
+        ..Default::default()
+    };
+    parse_timeseries_events(
+        document_reader(xml_text.as_bytes())?,
+        vec![label],
+        vec![],
+        period_name,
+        &options,
+        |_, _, timestamp, values, resolution| callback(resolution, timestamp, &values[label]),
+    )?;
+    Ok(())
+}
+
+/// Parses the `label` values of the `TimeSeries` at `index` in the document, the points of other series being skipped
+/// as they are read.
+pub fn parse_timeseries_nth(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+    index: usize,
+) -> Result<TimeSeries, DenkiError> {
+    let mut document = parse_timeseries_events(
+        document_reader(xml_text.as_bytes())?,
+        vec![label],
+        vec![],
+        period_name,
+        &ParseOptions::default(),
+        |series_index, series, timestamp, values, resolution| {
+            if series_index == index {
+                series.push_point(timestamp, values, resolution);
+            }
+            ControlFlow::Continue(())
+        },
+    )?;
+    let count = document.series.len();
+    if index >= count {
+        return Err(DenkiError::Invalid(format!(
+            "TimeSeries index {index} out of range, document has {count} series"
+        )));
+    }
+    Ok(document.series.swap_remove(index))
+}
+
+/// Merges the outputs of `parse_timeseries_generic` on several documents, e.g. of consecutive time windows, in
+/// timestamp order.
+///
+/// Points sharing a timestamp and resolution, e.g. at the boundary of two windows, are kept once, from the last
+/// result. Columns missing from a result are filled with `Data::Null`. Fails on a result without a `timestamp`
+/// column, whose points could not be ordered.
+pub fn merge_timeseries(results: Vec<HashMap<String, Vec<Data>>>) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    let mut merged: HashMap<String, Vec<Data>> = HashMap::new();
+    let mut len = 0;
+
+    for result in results {
+        let result_len = result.values().map(Vec::len).max().unwrap_or(0);
+        if result_len > 0 && !result.contains_key("timestamp") {
+            return Err(DenkiError::Invalid(
+                "cannot merge a result without a timestamp column".to_string(),
+            ));
+        }
+        for (k, mut v) in result {
+            v.resize(result_len, Data::Null);
+            let column = merged.entry(k).or_insert_with(|| vec![Data::Null; len]);
+            column.extend(v);
+        }
+        len += result_len;
+        for column in merged.values_mut() {
+            column.resize(len, Data::Null);
+        }
+    }
+
+    let mut series = TimeSeries {
+        data: merged,
+        ..Default::default()
+    };
+    series.sort_points()?;
+    Ok(series.data)
+}
+
+/// Serializes a parsed document to JSON, e.g. for snapshot tests. `Data` values are tagged with their variant, e.g.
+/// `{"F64": 104.98}`, so that they deserialize back to the same variant.
+#[cfg(feature = "serde")]
+pub fn to_json(document: &Document) -> Result<String, DenkiError> {
+    serde_json::to_string(document).map_err(|e| DenkiError::Invalid(e.to_string()))
+}
+
+/// Numeric `label` values of a series along with their timestamp and resolution.
+fn numeric_points<'a>(series: &'a TimeSeries, label: &str) -> Result<Vec<(Timestamp, f64, &'a str)>, DenkiError> {
+    let (Some(timestamps), Some(values), Some(resolutions)) = (
+        series.data.get("timestamp"),
+        series.data.get(label),
+        series.data.get("resolution"),
+    ) else {
+        return Ok(Vec::new());
+    };
+    let mut points = Vec::with_capacity(timestamps.len());
+
+    for ((timestamp, value), resolution) in timestamps.iter().zip(values).zip(resolutions) {
+        let (Data::Timestamp(timestamp), Data::String(resolution)) = (timestamp, resolution) else {
+            continue;
+        };
+        let value = match value {
+            Data::F64(value) => *value,
+            Data::I64(value) => *value as f64,
+            Data::Null => f64::NAN,
+            _ => {
+                return Err(DenkiError::Invalid(format!(
+                    "non-numeric value for label {label}: {value:?}"
+                )))
+            }
+        };
+        points.push((*timestamp, value, resolution.as_str()));
+    }
+
+    Ok(points)
+}
+
+/// Format of the `parse_timeseries_multi_with_keys` keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyFormat {
+    /// Between the parts of a key, e.g. `PT60M_price.amount`.
+    pub separator: String,
+    /// Prefixes the keys with the index of their `TimeSeries`, e.g. `0_PT60M_price.amount`, so that series sharing a
+    /// resolution are not merged.
+    pub series_index: bool,
+    /// Adds the `{resolution}_position` columns, see `ParseOptions::keep_positions`.
+    pub positions: bool,
+}
+
+impl Default for KeyFormat {
+    fn default() -> Self {
+        Self {
+            separator: "_".to_string(),
+            series_index: false,
+            positions: false,
+        }
+    }
+}
+
+/// Parses several labels in a single pass, keyed by `{resolution}_{label}`, along with `{resolution}_timestamp`.
+///
+/// Points missing a label hold `Data::Null` for it, so that the columns of a resolution stay aligned.
+pub fn parse_timeseries_multi(
+    xml_text: &str,
+    labels: &[&str],
+    period_name: &str,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    parse_timeseries_multi_with_keys(xml_text, labels, period_name, &KeyFormat::default())
+}
+
+/// Same as `parse_timeseries_multi`, with keys formatted according to `keys`.
+pub fn parse_timeseries_multi_with_keys(
+    xml_text: &str,
+    labels: &[&str],
+    period_name: &str,
+    keys: &KeyFormat,
+) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    let options = ParseOptions {
+        keep_positions: keys.positions,
+        ..Default::default()
+    };
+    let document = try_parse_timeseries_document(xml_text, labels.to_vec(), vec![], period_name, &options)?;
+    let mut data: HashMap<String, Vec<Data>> = HashMap::new();
+    let separator = &keys.separator;
+
+    for (index, series) in document.series.iter().enumerate() {
+        let (Some(timestamps), Some(resolutions)) = (series.data.get("timestamp"), series.data.get("resolution"))
+        else {
+            continue;
+        };
+        for (i, resolution) in resolutions.iter().enumerate() {
+            let Data::String(resolution) = resolution else {
+                continue;
+            };
+            let prefix = if keys.series_index {
+                format!("{index}{separator}{resolution}{separator}")
+            } else {
+                format!("{resolution}{separator}")
+            };
+            data.entry(format!("{prefix}timestamp"))
+                .or_default()
+                .push(timestamps[i].clone());
+            for label in labels {
+                data.entry(format!("{prefix}{label}"))
+                    .or_default()
+                    .push(series.data[*label][i].clone());
+            }
+            if keys.positions {
+                data.entry(format!("{prefix}position"))
+                    .or_default()
+                    .push(series.data["position"][i].clone());
+            }
+        }
+    }
+
+    Ok(data)
+}
+
+/// Parses the `label` values of a document into typed columns, keyed by resolution.
+pub fn parse_timeseries_columns(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+) -> Result<HashMap<String, Columns>, DenkiError> {
+    let document = try_parse_timeseries_document(xml_text, vec![label], vec![], period_name, &ParseOptions::default())?;
+    let mut columns: HashMap<String, Columns> = HashMap::new();
+
+    for series in document.series.iter() {
+        for (timestamp, value, resolution) in numeric_points(series, label)? {
+            let resolution_columns = columns.entry(resolution.to_string()).or_default();
+            resolution_columns.timestamp.push(timestamp);
+            resolution_columns.value.push(value);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// How `parse_timeseries_resampled` combines the points of a finer resolution falling in one coarser step.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Aggregation {
+    #[default]
+    Mean,
+    Sum,
+}
+
+/// Fixed length of a resolution in whole seconds, e.g. `900` for `PT15M`, failing below one second.
+fn resolution_seconds(resolution: &str) -> Result<i64, DenkiError> {
+    let seconds = resolution_to_timedelta(resolution)?
+        .total(jiff::Unit::Second)
+        .map_err(|_| DenkiError::Unsupported(format!("cannot resample resolution {resolution} of variable length")))?;
+    if seconds < 1.0 {
+        return Err(DenkiError::Unsupported(format!(
+            "cannot resample resolution {resolution} shorter than a second"
+        )));
+    }
+    Ok(seconds as i64)
+}
+
+/// Parses the `label` values of a document onto its coarsest resolution, e.g. four `PT15M` points into one `PT60M`
+/// value, combined by `aggregation`.
+///
+/// The steps are aligned on the first point of the coarsest resolution. A step holding a point of the coarsest
+/// resolution keeps it rather than the aggregate of finer points. Fails when the coarsest resolution is not an integer
+/// multiple of the others.
+pub fn parse_timeseries_resampled(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+    aggregation: Aggregation,
+) -> Result<Columns, DenkiError> {
+    let columns = parse_timeseries_columns(xml_text, label, period_name)?;
+    let mut steps = Vec::with_capacity(columns.len());
+    for resolution in columns.keys() {
+        steps.push((resolution_seconds(resolution)?, resolution.as_str()));
+    }
+    let Some(&(coarse, coarse_resolution)) = steps.iter().max() else {
+        return Ok(Columns::default());
+    };
+    if let Some((_, resolution)) = steps.iter().find(|(step, _)| coarse % step != 0) {
+        return Err(DenkiError::Unsupported(format!(
+            "cannot resample {resolution} onto {coarse_resolution}, which is not an integer multiple of it"
+        )));
+    }
+
+    let origin = columns[coarse_resolution]
+        .timestamp
+        .first()
+        .map_or(0, |timestamp| timestamp.as_second());
+    let mut buckets: BTreeMap<i64, (Option<f64>, Vec<f64>)> = BTreeMap::new();
+    for (resolution, resolution_columns) in columns.iter() {
+        for (timestamp, value) in resolution_columns.timestamp.iter().zip(&resolution_columns.value) {
+            let offset = timestamp.as_second() - origin;
+            let bucket = buckets.entry(origin + offset.div_euclid(coarse) * coarse).or_default();
+            if resolution == coarse_resolution {
+                bucket.0 = Some(*value);
+            } else {
+                bucket.1.push(*value);
+            }
+        }
+    }
+
+    let mut resampled = Columns::default();
+    for (second, (coarse_value, fine_values)) in buckets {
+        let value = coarse_value.unwrap_or_else(|| {
+            let sum: f64 = fine_values.iter().sum();
+            match aggregation {
+                Aggregation::Mean => sum / fine_values.len() as f64,
+                Aggregation::Sum => sum,
+            }
+        });
+        resampled.timestamp.push(Timestamp::from_second(second)?);
+        resampled.value.push(value);
+    }
+    Ok(resampled)
+}
+
+/// Parses the `label` values of a document to one record per point, with its `timestamp`, `resolution` and `value`,
+/// in document order.
+pub fn parse_timeseries_records(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+) -> Result<Vec<HashMap<String, Data>>, DenkiError> {
+    let document = try_parse_timeseries_document(xml_text, vec![label], vec![], period_name, &ParseOptions::default())?;
+    let mut records = Vec::new();
+
+    for series in document.series.iter() {
+        let (Some(timestamps), Some(resolutions), Some(values)) = (
+            series.data.get("timestamp"),
+            series.data.get("resolution"),
+            series.data.get(label),
+        ) else {
+            continue;
+        };
+        for ((timestamp, resolution), value) in timestamps.iter().zip(resolutions).zip(values) {
+            records.push(HashMap::from([
+                ("timestamp".to_string(), timestamp.clone()),
+                ("resolution".to_string(), resolution.clone()),
+                ("value".to_string(), value.clone()),
+            ]));
+        }
+    }
+
+    Ok(records)
+}
+
+/// Writes records of `parse_timeseries_records` as CSV, with a `timestamp,value` header and RFC 3339 timestamps.
+///
+/// A `resolution` column is added between them when the records span several resolutions.
+pub fn to_csv<W: Write>(records: &[HashMap<String, Data>], mut writer: W) -> std::io::Result<()> {
+    let field = |record: &HashMap<String, Data>, key: &str| record.get(key).map_or_else(String::new, csv_field);
+    let mut resolutions = records.iter().map(|record| field(record, "resolution"));
+    let multiple_resolutions = resolutions.next().is_some_and(|first| resolutions.any(|r| r != first));
+
+    if multiple_resolutions {
+        writeln!(writer, "timestamp,resolution,value")?;
+    } else {
+        writeln!(writer, "timestamp,value")?;
+    }
+    for record in records {
+        if multiple_resolutions {
+            writeln!(
+                writer,
+                "{},{},{}",
+                field(record, "timestamp"),
+                field(record, "resolution"),
+                field(record, "value")
+            )?;
+        } else {
+            writeln!(writer, "{},{}", field(record, "timestamp"), field(record, "value"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses the `label` values of a document to CSV, as written by `to_csv`.
+pub fn parse_timeseries_to_csv(xml_text: &str, label: &str, period_name: &str) -> Result<String, DenkiError> {
+    let records = parse_timeseries_records(xml_text, label, period_name)?;
+    let mut csv = Vec::new();
+    to_csv(&records, &mut csv)?;
+    String::from_utf8(csv).map_err(|e| DenkiError::Invalid(e.to_string()))
+}
+
+/// Label and period name of the values of a document type, as parsed by `parse_auto`.
+fn auto_labels(document_type: &str) -> Option<(&'static str, &'static str)> {
+    match document_type {
+        "A44" => Some(("price.amount", "Period")),
+        "A85" => Some(("imbalance_Price.amount", "Period")),
+        "A09" | "A11" | "A61" | "A65" | "A68" | "A69" | "A70" | "A71" | "A72" | "A73" | "A74" | "A75" | "A83"
+        | "A86" => Some(("quantity", "Period")),
+        "A76" | "A77" | "A78" | "A79" | "A80" => Some(("quantity", "Available_Period")),
+        _ => None,
+    }
+}
+
+/// Same as `parse_timeseries_generic`, with the label and period name picked from the document `type`, e.g.
+/// `price.amount` for `A44` prices.
+///
+/// Documents of other types must be parsed with `parse_timeseries_generic` and an explicit label and period name.
+pub fn parse_auto(xml_text: &str) -> Result<HashMap<String, Vec<Data>>, DenkiError> {
+    let document_type =
+        document_type(xml_text)?.ok_or_else(|| DenkiError::Unsupported("document has no type element".to_string()))?;
+    let (label, period_name) = auto_labels(&document_type).ok_or_else(|| {
+        DenkiError::Unsupported(format!(
+            "unsupported document type {document_type}, label and period name must be given explicitly"
+        ))
+    })?;
+    parse_timeseries_generic(xml_text, vec![label], vec![], period_name)
+}
+
+/// Parses the `quantity` values of a load document, e.g. `A65` system total load.
+pub fn parse_load(xml_text: &str) -> Result<Load, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    let process_type = document.metadata.process_type.clone();
+    load_series(&document, process_type, |_| true)
+}
+
+/// Parses the day-ahead total load forecast (`A65` with `processType` `A01`) of one or several concatenated documents,
+/// ignoring the series of other processes, e.g. realised values.
+pub fn parse_total_load_forecast(xml_text: &str) -> Result<Load, DenkiError> {
+    parse_load_process(xml_text, "A01")
+}
+
+/// Parses the actual total load (`A65` with `processType` `A16`) of one or several concatenated documents, ignoring
+/// the series of other processes, e.g. day-ahead forecasts.
+pub fn parse_actual_total_load(xml_text: &str) -> Result<Load, DenkiError> {
+    parse_load_process(xml_text, "A16")
+}
+
+fn parse_load_process(xml_text: &str, process_type: &str) -> Result<Load, DenkiError> {
+    let document = parse_timeseries_multidoc(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    load_series(&document, Some(process_type.to_string()), |series| {
+        series.process_type.as_deref() == Some(process_type)
+    })
+}
+
+/// Integer `quantity` values of the series of `document` accepted by `filter`.
+fn load_series(
+    document: &Document,
+    process_type: Option<String>,
+    filter: impl Fn(&TimeSeries) -> bool,
+) -> Result<Load, DenkiError> {
+    let mut load = Load {
+        process_type,
+        ..Default::default()
+    };
+
+    for series in document.series.iter().filter(|series| filter(series)) {
+        let (Some(timestamps), Some(quantities)) = (series.data.get("timestamp"), series.data.get("quantity")) else {
+            continue;
+        };
+        for (timestamp, quantity) in timestamps.iter().zip(quantities) {
+            let Data::Timestamp(timestamp) = timestamp else {
+                continue;
+            };
+            let quantity = match quantity {
+                Data::I64(quantity) => *quantity,
+                Data::F64(quantity) if quantity.fract() == 0.0 => *quantity as i64,
+                _ => {
+                    return Err(DenkiError::Invalid(format!(
+                        "non-integer quantity at {timestamp}: {quantity:?}"
+                    )))
+                }
+            };
+            load.timestamp.push(*timestamp);
+            load.quantity.push(quantity);
+        }
+    }
+
+    Ok(load)
+}
+
+/// Parses the `quantity` values of a reservoir filling document (`A72`), one per week in `P7D` resolution.
+pub fn parse_reservoir(xml_text: &str) -> Result<Reservoir, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    let mut reservoir = Reservoir::default();
+
+    for series in document.series.iter() {
+        reservoir.domain = reservoir.domain.or_else(|| series.in_domain.clone());
+        reservoir.measure_unit = reservoir.measure_unit.or_else(|| series.measure_unit.clone());
+        let (Some(timestamps), Some(quantities)) = (series.data.get("timestamp"), series.data.get("quantity")) else {
+            continue;
+        };
+        for (timestamp, quantity) in timestamps.iter().zip(quantities) {
+            let Data::Timestamp(timestamp) = timestamp else {
+                continue;
+            };
+            let stored_energy = match quantity {
+                Data::F64(quantity) => *quantity,
+                Data::I64(quantity) => *quantity as f64,
+                _ => {
+                    return Err(DenkiError::Invalid(format!(
+                        "non-numeric quantity at {timestamp}: {quantity:?}"
+                    )))
+                }
+            };
+            reservoir.timestamp.push(*timestamp);
+            reservoir.stored_energy.push(stored_energy);
+        }
+    }
+
+    Ok(reservoir)
+}
+
+/// `(timestamp, value)` points of the `label` values of a single resolution document, in timestamp order.
+fn parse_point_series(xml_text: &str, label: &str) -> Result<Vec<(Timestamp, f64)>, DenkiError> {
+    let columns = parse_timeseries_columns(xml_text, label, "Period")?;
+    if columns.len() > 1 {
+        let mut resolutions: Vec<&str> = columns.keys().map(String::as_str).collect();
+        resolutions.sort();
+        return Err(DenkiError::Invalid(format!(
+            "document has several resolutions ({}), see `parse_timeseries_columns`",
+            resolutions.join(", ")
+        )));
+    }
+    let mut points: Vec<(Timestamp, f64)> = columns
+        .into_values()
+        .flat_map(|columns| columns.timestamp.into_iter().zip(columns.value))
+        .collect();
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+    Ok(points)
+}
+
+/// Parses the `price.amount` values of a single resolution document, e.g. day-ahead prices, into `(timestamp, price)`
+/// points in timestamp order.
+pub fn parse_price_series(xml_text: &str) -> Result<Vec<(Timestamp, f64)>, DenkiError> {
+    parse_point_series(xml_text, "price.amount")
+}
+
+/// Parses the `quantity` values of a single resolution document, e.g. a load, into `(timestamp, quantity)` points in
+/// timestamp order.
+pub fn parse_quantity_series(xml_text: &str) -> Result<Vec<(Timestamp, f64)>, DenkiError> {
+    parse_point_series(xml_text, "quantity")
+}
+
+/// Parses the `label` values of a document into typed columns, keyed by the `psrType` of each `TimeSeries`.
+///
+/// Series without a `psrType` are skipped. See `codes::psr_type_name` for the meaning of the codes.
+pub fn parse_timeseries_by_psr_type(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+) -> Result<HashMap<String, Columns>, DenkiError> {
+    let document = try_parse_timeseries_document(xml_text, vec![label], vec![], period_name, &ParseOptions::default())?;
+    let mut columns: HashMap<String, Columns> = HashMap::new();
+
+    for series in document.series.iter() {
+        let Some(psr_type) = &series.psr_type else {
+            continue;
+        };
+        // Consumption, e.g. pumping, is kept apart from the generation of the same production type.
+        let key = match series.energy_flow.as_deref() {
+            Some("consumption") => format!("{psr_type}_consumption"),
+            _ => psr_type.clone(),
+        };
+        let psr_type_columns = columns.entry(key).or_default();
+        for (timestamp, value, _) in numeric_points(series, label)? {
+            psr_type_columns.timestamp.push(timestamp);
+            psr_type_columns.value.push(value);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Parses the `quantity` values of a wind and solar forecast document (`A69`), keyed by `psrType`.
+///
+/// With `align`, the values are also laid out on the timestamps shared by every `psrType`.
+pub fn parse_generation_forecast(xml_text: &str, align: bool) -> Result<GenerationForecast, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    let mut forecast = GenerationForecast {
+        process_type: document.metadata.process_type,
+        ..Default::default()
+    };
+    let mut grid: BTreeMap<Timestamp, HashMap<&str, f64>> = BTreeMap::new();
+
+    for series in document.series.iter() {
+        let Some(psr_type) = &series.psr_type else {
+            continue;
+        };
+        let psr_type_columns = forecast.series.entry(psr_type.clone()).or_default();
+        for (timestamp, value, _) in numeric_points(series, "quantity")? {
+            psr_type_columns.timestamp.push(timestamp);
+            psr_type_columns.value.push(value);
+            if align {
+                grid.entry(timestamp).or_default().insert(psr_type, value);
+            }
+        }
+    }
+
+    if align {
+        forecast.timestamp = grid.keys().copied().collect();
+        for psr_type in forecast.series.keys() {
+            let values = grid
+                .values()
+                .map(|values| {
+                    values
+                        .get(psr_type.as_str())
+                        .map_or(Data::Null, |value| Data::F64(*value))
+                })
+                .collect();
+            forecast.aligned.insert(psr_type.clone(), values);
+        }
+    }
+
+    Ok(forecast)
+}
+
+/// Parses the yearly `quantity` values of an installed capacity document, keyed by `psrType`.
+///
+/// Capacities of the units sharing a `psrType`, e.g. in a per unit document, are summed per year.
+pub fn parse_installed_capacity(xml_text: &str) -> Result<HashMap<String, Columns>, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    let mut capacities: HashMap<String, BTreeMap<Timestamp, f64>> = HashMap::new();
+
+    for series in document.series.iter() {
+        let Some(psr_type) = &series.psr_type else {
+            continue;
+        };
+        let psr_type_capacities = capacities.entry(psr_type.clone()).or_default();
+        for (timestamp, value, _) in numeric_points(series, "quantity")? {
+            *psr_type_capacities.entry(timestamp).or_default() += value;
+        }
+    }
+
+    Ok(capacities
+        .into_iter()
+        .map(|(psr_type, capacities)| {
+            let columns = Columns {
+                timestamp: capacities.keys().copied().collect(),
+                value: capacities.values().copied().collect(),
+            };
+            (psr_type, columns)
+        })
+        .collect())
+}
+
+/// Parses the `imbalance_Price.amount` values of an imbalance price document, keyed by flow direction.
+///
+/// Series without a `flowDirection.direction` are skipped.
+pub fn parse_imbalance_prices(xml_text: &str) -> Result<ImbalancePrices, DenkiError> {
+    let document = try_parse_timeseries_document(
+        xml_text,
+        vec!["imbalance_Price.amount"],
+        vec![],
+        "Period",
+        &ParseOptions::default(),
+    )?;
+    let mut prices = ImbalancePrices::default();
+
+    for series in document.series.iter() {
+        let Some(direction) = &series.flow_direction else {
+            continue;
+        };
+        prices.currency = prices.currency.or_else(|| series.currency.clone());
+        prices.measure_unit = prices.measure_unit.or_else(|| series.measure_unit.clone());
+        let direction_columns = prices.directions.entry(direction.clone()).or_default();
+        for (timestamp, value, _) in numeric_points(series, "imbalance_Price.amount")? {
+            direction_columns.timestamp.push(timestamp);
+            direction_columns.value.push(value);
+        }
+    }
+
+    Ok(prices)
+}
+
+/// Groups the `label` values of a document per `(out_domain, in_domain)` direction, in document order.
+fn directional_flows(document: &Document, label: &str) -> Result<Vec<Flow>, DenkiError> {
+    let mut directions: Vec<Flow> = Vec::new();
+
+    for series in document.series.iter() {
+        let position = directions
+            .iter()
+            .position(|flow| flow.out_domain == series.out_domain && flow.in_domain == series.in_domain);
+        let flow = match position {
+            Some(position) => &mut directions[position],
+            None => {
+                directions.push(Flow {
+                    out_domain: series.out_domain.clone(),
+                    in_domain: series.in_domain.clone(),
+                    ..Default::default()
+                });
+                directions.last_mut().unwrap()
+            }
+        };
+        for (timestamp, value, _) in numeric_points(series, label)? {
+            flow.timestamp.push(timestamp);
+            flow.value.push(value);
+        }
+    }
+
+    Ok(directions)
+}
+
+/// Parses the `quantity` values of a scheduled commercial exchanges document (`A09`) per direction.
+pub fn parse_scheduled_exchanges(xml_text: &str) -> Result<Vec<Flow>, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    directional_flows(&document, "quantity")
+}
+
+/// Parses the `quantity` values of a net transfer capacity document per direction and market, in document order.
+pub fn parse_net_transfer_capacity(xml_text: &str) -> Result<Vec<TransferCapacity>, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    let mut capacities: Vec<TransferCapacity> = Vec::new();
+
+    for series in document.series.iter() {
+        let position = capacities.iter().position(|capacity| {
+            capacity.out_domain == series.out_domain
+                && capacity.in_domain == series.in_domain
+                && capacity.contract_agreement_type == series.contract_agreement_type
+        });
+        let capacity = match position {
+            Some(position) => &mut capacities[position],
+            None => {
+                capacities.push(TransferCapacity {
+                    out_domain: series.out_domain.clone(),
+                    in_domain: series.in_domain.clone(),
+                    contract_agreement_type: series.contract_agreement_type.clone(),
+                    ..Default::default()
+                });
+                capacities.last_mut().unwrap()
+            }
+        };
+        for (timestamp, value, _) in numeric_points(series, "quantity")? {
+            capacity.timestamp.push(timestamp);
+            capacity.value.push(value);
+        }
+    }
+
+    Ok(capacities)
+}
+
+/// Parses the `quantity` values of an activated balancing energy document per direction and reserve type, in document
+/// order.
+pub fn parse_activated_energy(xml_text: &str) -> Result<Vec<ActivatedEnergy>, DenkiError> {
+    let document =
+        try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())?;
+    let mut activations: Vec<ActivatedEnergy> = Vec::new();
+
+    for series in document.series.iter() {
+        let position = activations.iter().position(|activation| {
+            activation.flow_direction == series.flow_direction && activation.business_type == series.business_type
+        });
+        let activation = match position {
+            Some(position) => &mut activations[position],
+            None => {
+                activations.push(ActivatedEnergy {
+                    flow_direction: series.flow_direction.clone(),
+                    business_type: series.business_type.clone(),
+                    measure_unit: series.measure_unit.clone(),
+                    ..Default::default()
+                });
+                activations.last_mut().unwrap()
+            }
+        };
+        for (timestamp, value, _) in numeric_points(series, "quantity")? {
+            activation.timestamp.push(timestamp);
+            activation.value.push(value);
+        }
+    }
+
+    Ok(activations)
+}
+
+/// Parses the `label` values of a cross-border document per direction, netting opposite directions.
+pub fn parse_flows(xml_text: &str, label: &str, period_name: &str) -> Result<Flows, DenkiError> {
+    let document = try_parse_timeseries_document(xml_text, vec![label], vec![], period_name, &ParseOptions::default())?;
+    let mut flows = Flows {
+        directions: directional_flows(&document, label)?,
+        ..Default::default()
+    };
+
+    for (i, flow) in flows.directions.iter().enumerate() {
+        let Some(opposite) = flows
+            .directions
+            .iter()
+            .skip(i + 1)
+            .find(|opposite| opposite.out_domain == flow.in_domain && opposite.in_domain == flow.out_domain)
+        else {
+            continue;
+        };
+        let mut net: BTreeMap<Timestamp, f64> = BTreeMap::new();
+        for (timestamp, value) in flow.timestamp.iter().zip(&flow.value) {
+            *net.entry(*timestamp).or_default() += value;
+        }
+        for (timestamp, value) in opposite.timestamp.iter().zip(&opposite.value) {
+            *net.entry(*timestamp).or_default() -= value;
+        }
+        flows.net.push(Flow {
+            out_domain: flow.out_domain.clone(),
+            in_domain: flow.in_domain.clone(),
+            timestamp: net.keys().copied().collect(),
+            value: net.values().copied().collect(),
+        });
+    }
+
+    Ok(flows)
+}
+
+/// Parses the outages of an `Unavailability_MarketDocument`, one per `Available_Period` point.
+///
+/// A point holds from its position until the next point of the period, or the end of the period.
+pub fn parse_unavailability(xml_text: &str) -> Result<Vec<Outage>, DenkiError> {
+    let mut outages: Vec<Outage> = Vec::new();
+    let mut document_reason = Reason::default();
+    let mut series = Outage {
+        mrid: None,
+        business_type: None,
+        asset_mrid: None,
+        start: Timestamp::UNIX_EPOCH,
+        end: Timestamp::UNIX_EPOCH,
+        quantity: 0.0,
+        reason: Reason::default(),
+    };
+    let mut series_outages: Vec<Outage> = Vec::new();
+
+    let mut current_period_start: Option<String> = None;
+    let mut current_period_end: Option<String> = None;
+    let mut current_period_resolution: Option<String> = None;
+    let mut current_position: Option<i64> = None;
+    let mut current_quantity: Option<f64> = None;
+    let mut current_points: Vec<(i64, f64)> = Vec::new();
+    let mut elements = Elements::default();
+    let mut text = String::new();
+    let mut reader = XmlReader::new(xml_text.as_bytes())?;
+
+    loop {
+        match reader.next_markup(&mut text)? {
+            Markup::Start { name, .. } => {
+                if name.local_name == "TimeSeries" {
+                    series.mrid = None;
+                    series.business_type = None;
+                    series.asset_mrid = None;
+                    series.reason = Reason::default();
+                } else if name.local_name == "Available_Period" {
+                    current_period_start = None;
+                    current_period_end = None;
+                    current_period_resolution = None;
+                    current_points.clear();
+                } else if name.local_name == "Point" {
+                    current_position = None;
+                    current_quantity = None;
+                }
+                elements.push(name.local_name);
+                text.clear();
+            }
+            Markup::End(name) => {
+                let element = name.local_name;
+                let parent = elements.parent();
+                match (parent, element) {
+                    (Some("TimeSeries"), "mRID") => series.mrid = Some(text.clone()),
+                    (Some("TimeSeries"), "businessType") => series.business_type = Some(text.clone()),
+                    (Some("TimeSeries"), "production_RegisteredResource.mRID")
+                    | (Some("Asset_RegisteredResource"), "mRID") => {
+                        series.asset_mrid.get_or_insert_with(|| text.clone());
+                    }
+                    (Some("Reason"), "code" | "text") => {
+                        let in_series = elements.contains("TimeSeries");
+                        let reason = if in_series {
+                            &mut series.reason
+                        } else {
+                            &mut document_reason
+                        };
+                        let field = if element == "code" {
+                            &mut reason.code
+                        } else {
+                            &mut reason.text
+                        };
+                        field.get_or_insert_with(|| text.clone());
+                    }
+                    (Some("timeInterval"), "start") => current_period_start = Some(text.clone()),
+                    (Some("timeInterval"), "end") => current_period_end = Some(text.clone()),
+                    (_, "resolution") => current_period_resolution = Some(text.clone()),
+                    // Positions and quantities may be padded with whitespace or newlines, as in time series.
+                    (Some("Point"), "position") => {
+                        let position = text
+                            .trim()
+                            .parse()
+                            .map_err(|_| DenkiError::Invalid(format!("invalid position {text:?}")))?;
+                        current_position = Some(position);
+                    }
+                    (Some("Point"), "quantity") => {
+                        let quantity = text
+                            .trim()
+                            .parse()
+                            .map_err(|_| DenkiError::Invalid(format!("invalid quantity {text:?}")))?;
+                        current_quantity = Some(quantity);
+                    }
+                    (_, "Point") => {
+                        if let (Some(position), Some(quantity)) = (current_position, current_quantity) {
+                            current_points.push((position, quantity));
+                        }
+                    }
+                    (_, "Available_Period") => {
+                        let (Some(start), Some(end), Some(resolution)) =
+                            (&current_period_start, &current_period_end, &current_period_resolution)
+                        else {
+                            return Err(DenkiError::Invalid(
+                                "Available_Period without a timeInterval or resolution".to_string(),
+                            ));
+                        };
+                        let period_end = interval_timestamp(end)?;
+                        current_points.sort_by_key(|(position, _)| *position);
+                        for (i, (position, quantity)) in current_points.iter().enumerate() {
+                            let segment_end = match current_points.get(i + 1) {
+                                Some((next_position, _)) => {
+                                    point_timestamp(start, resolution, *next_position, &TimeZone::UTC)?
+                                }
+                                None => period_end,
+                            };
+                            series_outages.push(Outage {
+                                start: point_timestamp(start, resolution, *position, &TimeZone::UTC)?,
+                                end: segment_end,
+                                quantity: *quantity,
+                                ..series.clone()
+                            });
+                        }
+                    }
+                    (_, "TimeSeries") => {
+                        // The reason may follow the periods of the series.
+                        for outage in series_outages.iter_mut() {
+                            outage.reason = series.reason.clone();
+                        }
+                        outages.append(&mut series_outages);
+                    }
+                    _ => {}
+                }
+                elements.pop();
+                text.clear();
+            }
+            Markup::Eof => break,
+        }
+    }
+
+    for outage in outages.iter_mut() {
+        if outage.reason.code.is_none() {
+            outage.reason = document_reason.clone();
+        }
+    }
+
+    Ok(outages)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, ops::ControlFlow};
+
+    use super::{
+        document_reader, parse_timeseries_events, parse_timeseries_generic_reader, reject_html, Document, Markup,
+        MarkupReader, Name,
+    };
+    use super::{
+        document_type, for_each_point, interval_timestamp, merge_timeseries, parse_acknowledgement,
+        parse_activated_energy, parse_actual_total_load, parse_auto, parse_flows, parse_generation_forecast,
+        parse_imbalance_prices, parse_installed_capacity, parse_load, parse_net_transfer_capacity, parse_price_series,
+        parse_quantity_series, parse_reservoir, parse_scheduled_exchanges, parse_timeseries_by_psr_type,
+        parse_timeseries_columns, parse_timeseries_from_bytes, parse_timeseries_from_path, parse_timeseries_generic,
+        parse_timeseries_generic_with_options, parse_timeseries_multi, parse_timeseries_multi_with_keys,
+        parse_timeseries_multidoc, parse_timeseries_nth, parse_timeseries_records, parse_timeseries_resampled,
+        parse_timeseries_to_csv, parse_total_load_forecast, parse_unavailability, resolution_to_timedelta, to_csv,
+        to_csv_rows, try_parse_timeseries_document, ActivatedEnergy, Aggregation, Columns, Data, DenkiError,
+        ElementNames, Flow, KeyFormat, Load, ParseError, ParseOptions, ParseStats, Period, Reason, Reservoir,
+        TransferCapacity,
+    };
+    use crate::{codes::process_type_name, fixtures};
+    use jiff::{tz::TimeZone, Timestamp, ToSpan};
+    use std::io::{BufRead, BufReader, Read};
+    use xml::{
+        common::Position,
+        name::OwnedName,
+        reader::{EventReader, Events, XmlEvent},
+    };
+
+    /// `count` timestamps stepped by `minutes` from `start`.
+    fn grid(start: &str, minutes: i64, count: i64) -> Vec<Data> {
+        let start: Timestamp = start.parse().unwrap();
+        (0..count)
+            .map(|i| Data::Timestamp(start + (i * minutes).minutes()))
+            .collect()
+    }
+
+    /// `price.amount` of the day-ahead fixture, whose A03 curve holds its second point until the end of the day.
+    fn day_ahead_prices() -> Vec<Data> {
+        let mut prices = vec![Data::F64(104.98)];
+        prices.extend(vec![Data::F64(105.98); 23]);
+        prices
+    }
+
+    #[test]
+    fn test_parse_timeseries_generic_day_ahead_price() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <publication_marketdocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <mRID>bf4445f7e6e04c849b7e0830b906fbde</mRID>
+        <revisionnumber>1</revisionnumber>
+        <type>A44</type>
+        <sender_marketparticipant.mRID codingscheme="A01">10X1001A1001A450</sender_marketparticipant.mRID>
         <sender_marketparticipant.marketrole.type>A32</sender_marketparticipant.marketrole.type>
         <receiver_marketparticipant.mRID codingscheme="A01">10X1001A1001A450</receiver_marketparticipant.mRID>
         <receiver_marketparticipant.marketrole.type>A33</receiver_marketparticipant.marketrole.type>
@@ -152,268 +2689,3945 @@ mod tests {
             <end>2024-01-01T23:00Z</end>
         </period.timeInterval>
         <TimeSeries>
-            <mRID>1</mRID>
-            <auction.type>A01</auction.type>
-            <businessType>A62</businessType>
-            <in_Domain.mRID codingscheme="A01">10YFR-RTE------C</in_Domain.mRID>
-            <out_Domain.mRID codingscheme="A01">10YFR-RTE------C</out_Domain.mRID>
-            <contract_MarketAgreement.type>A01</contract_MarketAgreement.type>
-            <currency_Unit.name>EUR</currency_Unit.name>
-            <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+            <mRID>1</mRID>
+            <auction.type>A01</auction.type>
+            <businessType>A62</businessType>
+            <in_Domain.mRID codingscheme="A01">10YFR-RTE------C</in_Domain.mRID>
+            <out_Domain.mRID codingscheme="A01">10YFR-RTE------C</out_Domain.mRID>
+            <contract_MarketAgreement.type>A01</contract_MarketAgreement.type>
+            <currency_Unit.name>EUR</currency_Unit.name>
+            <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+            <curveType>A03</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </publication_marketdocument>
+        "#;
+
+        let result = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period");
+        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
+
+        let data = result.unwrap();
+        assert!(data.contains_key("timestamp"), "{}", format!("Keys: {:?}", data.keys()));
+        assert!(
+            data.contains_key("price.amount"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert!(
+            data.contains_key("resolution"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        // The last point of the A03 curve holds until the end of the period.
+        assert_eq!(data["timestamp"], grid("2023-12-31T23:00:00Z", 60, 24));
+        assert_eq!(data["price.amount"], day_ahead_prices());
+        assert_eq!(data["resolution"], vec![Data::String("PT60M".to_string()); 24]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_coding_schemes() {
+        let document = try_parse_timeseries_document(
+            fixtures::day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.metadata.coding_schemes,
+            HashMap::from([
+                ("sender_marketparticipant.mRID".to_string(), "A01".to_string()),
+                ("receiver_marketparticipant.mRID".to_string(), "A01".to_string()),
+            ])
+        );
+        assert_eq!(
+            document.series[0]
+                .coding_schemes
+                .get("in_Domain.mRID")
+                .map(String::as_str),
+            Some("A01")
+        );
+        assert_eq!(document.series[0].coding_schemes.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_timeseries_stats() {
+        let document = try_parse_timeseries_document(
+            fixtures::day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.stats,
+            ParseStats {
+                series: 1,
+                periods: 1,
+                points: 24,
+                skipped: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_agreement_types() {
+        let document = try_parse_timeseries_document(
+            fixtures::day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(document.series[0].auction_type.as_deref(), Some("A01"));
+        assert_eq!(document.series[0].contract_agreement_type.as_deref(), Some("A01"));
+    }
+
+    #[test]
+    fn test_parse_timeseries_balancy_energy_price() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Balancing_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:balancingdocument:4:1">
+        <mRID>05e317126b4f46f3bb4af1f0314ca0e7</mRID>
+        <revisionNumber>1</revisionNumber>
+        <type>A84</type>
+        <process.processType>A16</process.processType>
+        <sender_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</sender_MarketParticipant.mRID>
+        <sender_MarketParticipant.marketRole.type>A32</sender_MarketParticipant.marketRole.type>
+        <receiver_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</receiver_MarketParticipant.mRID>
+        <receiver_MarketParticipant.marketRole.type>A33</receiver_MarketParticipant.marketRole.type>
+        <createdDateTime>2024-09-24T15:45:51Z</createdDateTime>
+        <area_Domain.mRID codingScheme="A01">10YBE----------2</area_Domain.mRID>
+        <period.timeInterval>
+            <start>2023-09-03T22:00Z</start>
+            <end>2023-09-04T22:00Z</end>
+        </period.timeInterval>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A96</businessType>
+            <original_MarketProduct.marketProductType>A02</original_MarketProduct.marketProductType>
+            <mktPSRType.psrType>A03</mktPSRType.psrType>
+            <flowDirection.direction>A01</flowDirection.direction>
+            <currency_Unit.name>EUR</currency_Unit.name>
+            <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+            <curveType>A03</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-09-03T22:00Z</start>
+                    <end>2023-09-04T22:00Z</end>
+                </timeInterval>
+                <resolution>PT15M</resolution>
+                <Point>
+                    <position>1</position>
+                    <activation_Price.amount>116.17</activation_Price.amount>
+                    <imbalance_Price.category>A06</imbalance_Price.category>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <activation_Price.amount>111.17</activation_Price.amount>
+                    <imbalance_Price.category>A06</imbalance_Price.category>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Balancing_MarketDocument>
+        "#;
+
+        let result = parse_timeseries_generic(
+            xml_text,
+            vec!["activation_Price.amount"],
+            vec!["flowDirection.direction", "businessType"],
+            "period",
+        );
+        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
+
+        let data = result.unwrap();
+        assert!(data.contains_key("timestamp"), "{}", format!("Keys: {:?}", data.keys()));
+        assert!(
+            data.contains_key("activation_Price.amount"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert!(
+            data.contains_key("flowDirection.direction"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert!(
+            data.contains_key("businessType"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert!(
+            data.contains_key("resolution"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert_eq!(data["timestamp"], grid("2023-09-03T22:00:00Z", 15, 96));
+        let mut prices = vec![Data::F64(116.17); 2];
+        prices.extend(vec![Data::F64(111.17); 94]);
+        assert_eq!(data["activation_Price.amount"], prices);
+        assert_eq!(data["resolution"], vec![Data::String("PT15M".to_string()); 96]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_installed_capacity_per_production_type() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <mRID>7c96d034d7e34768995d82b8602f4800</mRID>
+        <revisionNumber>1</revisionNumber>
+        <type>A68</type>
+        <process.processType>A33</process.processType>
+        <sender_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</sender_MarketParticipant.mRID>
+        <sender_MarketParticipant.marketRole.type>A32</sender_MarketParticipant.marketRole.type>
+        <receiver_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</receiver_MarketParticipant.mRID>
+        <receiver_MarketParticipant.marketRole.type>A33</receiver_MarketParticipant.marketRole.type>
+        <createdDateTime>2023-08-18T16:15:35Z</createdDateTime>
+        <time_Period.timeInterval>
+            <start>2022-12-31T23:00Z</start>
+            <end>2023-12-31T23:00Z</end>
+        </time_Period.timeInterval>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A37</businessType>
+            <objectAggregation>A08</objectAggregation>
+            <inBiddingZone_Domain.mRID codingScheme="A01">10YBE----------2</inBiddingZone_Domain.mRID>
+            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+            <curveType>A01</curveType>
+            <MktPSRType>
+                <psrType>B01</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2022-12-31T23:00Z</start>
+                    <end>2023-12-31T23:00Z</end>
+                </timeInterval>
+                <resolution>P1Y</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>712</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+
+        <TimeSeries>
+            <mRID>2</mRID>
+            <businessType>A37</businessType>
+            <objectAggregation>A08</objectAggregation>
+            <inBiddingZone_Domain.mRID codingScheme="A01">10YBE----------2</inBiddingZone_Domain.mRID>
+            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+            <curveType>A01</curveType>
+            <MktPSRType>
+                <psrType>B04</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2022-12-31T23:00Z</start>
+                    <end>2023-12-31T23:00Z</end>
+                </timeInterval>
+                <resolution>P1Y</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>6915</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>6913</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let result = parse_timeseries_generic(
+            xml_text,
+            vec!["quantity"],
+            vec!["quantity_Measure_Unit.name", "psrType"],
+            "period",
+        );
+        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
+
+        let data = result.unwrap();
+        assert!(data.contains_key("timestamp"), "{}", format!("Keys: {:?}", data.keys()));
+        assert!(data.contains_key("quantity"), "{}", format!("Keys: {:?}", data.keys()));
+        assert!(
+            data.contains_key("quantity_Measure_Unit.name"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert!(data.contains_key("psrType"), "{}", format!("Keys: {:?}", data.keys()));
+        assert!(
+            data.contains_key("resolution"),
+            "{}",
+            format!("Keys: {:?}", data.keys())
+        );
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2022-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2022-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(data["quantity"], vec![Data::I64(712), Data::I64(6915), Data::I64(6913)]);
+        assert_eq!(
+            data["resolution"],
+            vec![
+                Data::String("P1Y".to_string()),
+                Data::String("P1Y".to_string()),
+                Data::String("P1Y".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_unsupported_resolution() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </timeInterval>
+                <resolution>PX</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let result = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period");
+        assert!(result.is_err());
+        assert_eq!(result.err().unwrap().to_string(), "unsupported resolution: PX");
+    }
+
+    #[test]
+    fn test_resolution_to_timedelta() {
+        assert_eq!(resolution_to_timedelta("PT60M").unwrap().fieldwise(), 60.minutes());
+        assert_eq!(resolution_to_timedelta("P1Y").unwrap().fieldwise(), 1.year());
+        assert_eq!(resolution_to_timedelta("PT3H").unwrap().fieldwise(), 3.hours());
+        assert_eq!(resolution_to_timedelta("PT12H").unwrap().fieldwise(), 12.hours());
+        assert_eq!(resolution_to_timedelta("P2D").unwrap().fieldwise(), 2.days());
+        assert_eq!(resolution_to_timedelta("PT4S").unwrap().fieldwise(), 4.seconds());
+        assert!(resolution_to_timedelta("PX").is_err());
+        for resolution in ["PT0M", "P0D", "-PT60M"] {
+            assert_eq!(
+                resolution_to_timedelta(resolution).unwrap_err().to_string(),
+                format!("resolution must be positive: {resolution}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timeseries_iso_8601_resolution() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </timeInterval>
+                <resolution>PT3H</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T05:00:00Z".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_document_metadata() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <mRID>bf4445f7e6e04c849b7e0830b906fbde</mRID>
+        <type>A44</type>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <auction.type>A01</auction.type>
+            <businessType>A62</businessType>
+            <in_Domain.mRID codingScheme="A01">10YFR-RTE------C</in_Domain.mRID>
+            <out_Domain.mRID codingScheme="A01">10YFR-RTE------C</out_Domain.mRID>
+            <currency_Unit.name>EUR</currency_Unit.name>
+            <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+            <curveType>A03</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let result = try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec!["curveType"],
+            "period",
+            &ParseOptions::default(),
+        );
+        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
+
+        let document = result.unwrap();
+        assert_eq!(document.series.len(), 1);
+        let series = &document.series[0];
+        assert_eq!(series.mrid.as_deref(), Some("1"));
+        assert_eq!(series.business_type.as_deref(), Some("A62"));
+        assert_eq!(series.in_domain.as_deref(), Some("10YFR-RTE------C"));
+        assert_eq!(series.out_domain.as_deref(), Some("10YFR-RTE------C"));
+        assert_eq!(series.currency.as_deref(), Some("EUR"));
+        assert_eq!(series.measure_unit.as_deref(), Some("MWH"));
+        assert_eq!(series.metadata["curveType"], Data::String("A03".to_string()));
+        assert_eq!(series.data["price.amount"], vec![Data::F64(104.98); 24]);
+        assert_eq!(series.data["timestamp"], grid("2023-12-31T23:00:00Z", 60, 24));
+    }
+
+    #[test]
+    fn test_parse_timeseries_document_multiple_series() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A01</businessType>
+            <MktPSRType>
+                <psrType>B16</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>10</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>20</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        <TimeSeries>
+            <mRID>2</mRID>
+            <businessType>A01</businessType>
+            <MktPSRType>
+                <psrType>B19</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>30</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let document = try_parse_timeseries_document(
+            xml_text,
+            vec!["quantity"],
+            vec!["psrType"],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(document.series.len(), 2);
+
+        let (solar, wind) = (&document.series[0], &document.series[1]);
+        assert_eq!(solar.mrid.as_deref(), Some("1"));
+        assert_eq!(solar.metadata["psrType"], Data::String("B16".to_string()));
+        assert_eq!(solar.data["quantity"], vec![Data::I64(10), Data::I64(20)]);
+        assert_eq!(wind.mrid.as_deref(), Some("2"));
+        assert_eq!(wind.metadata["psrType"], Data::String("B19".to_string()));
+        assert_eq!(wind.data["quantity"], vec![Data::I64(30)]);
+        assert_eq!(
+            wind.data["timestamp"],
+            vec![Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap())]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_variable_sized_blocks_forward_fill() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <curveType>A03</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>4</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T02:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(
+            data["price.amount"],
+            vec![
+                Data::F64(104.98),
+                Data::F64(104.98),
+                Data::F64(104.98),
+                Data::F64(105.98)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_integer_and_float_values() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>2400</quantity>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["quantity", "price.amount"], vec![], "period").unwrap();
+        assert_eq!(data["quantity"], vec![Data::I64(2400)]);
+        assert_eq!(data["price.amount"], vec![Data::F64(104.98)]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_daily_resolution_across_dst() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2024-03-29T23:00Z</start>
+                    <end>2024-04-01T22:00Z</end>
+                </timeInterval>
+                <resolution>P1D</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>10</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>20</quantity>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <quantity>30</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let options = ParseOptions {
+            tz: TimeZone::get("Europe/Paris").unwrap(),
+            ..Default::default()
+        };
+        let data =
+            parse_timeseries_generic_with_options(xml_text, vec!["quantity"], vec![], "period", &options).unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2024-03-29T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-03-30T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-03-31T22:00:00Z".parse().unwrap()),
+            ]
+        );
+
+        let data = parse_timeseries_generic(xml_text, vec!["quantity"], vec![], "period").unwrap();
+        assert_eq!(
+            data["timestamp"][2],
+            Data::Timestamp("2024-03-31T23:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_text_split_by_entity() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104&#46;98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(data["price.amount"], vec![Data::F64(104.98)]);
+    }
+
+    #[test]
+    fn test_parse_acknowledgement() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+        <mRID>0b4a0bbd-b2de-4b0a-9c37-2c32a747cfd4</mRID>
+        <createdDateTime>2025-05-17T21:13:31Z</createdDateTime>
+        <sender_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</sender_MarketParticipant.mRID>
+        <sender_MarketParticipant.marketRole.type>A32</sender_MarketParticipant.marketRole.type>
+        <receiver_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</receiver_MarketParticipant.mRID>
+        <receiver_MarketParticipant.marketRole.type>A39</receiver_MarketParticipant.marketRole.type>
+        <received_MarketDocument.createdDateTime>2025-05-17T21:13:31Z</received_MarketDocument.createdDateTime>
+        <Reason>
+            <code>999</code>
+            <text>No matching data found</text>
+        </Reason>
+        </Acknowledgement_MarketDocument>
+        "#;
+
+        let reason = parse_acknowledgement(xml_text).unwrap();
+        assert_eq!(
+            reason,
+            Some(super::Reason {
+                code: Some("999".to_string()),
+                text: Some("No matching data found".to_string()),
+            })
+        );
+
+        let result = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period");
+        let error = result.err().unwrap();
+        assert_eq!(error.to_string(), "ENTSO-E error 999: No matching data found");
+        assert_eq!(error, DenkiError::Acknowledgement(reason.unwrap()));
+    }
+
+    #[test]
+    fn test_parse_acknowledgement_other_document() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <mRID>bf4445f7e6e04c849b7e0830b906fbde</mRID>
+        </Publication_MarketDocument>
+        "#;
+
+        assert_eq!(parse_acknowledgement(xml_text).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_timeseries_columns() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:30Z</end>
+                </timeInterval>
+                <resolution>PT15M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>100</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>101.5</price.amount>
+                </Point>
+            </Period>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T00:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let columns = parse_timeseries_columns(xml_text, "price.amount", "period").unwrap();
+        assert_eq!(columns.len(), 2);
+        for resolution_columns in columns.values() {
+            assert_eq!(resolution_columns.timestamp.len(), resolution_columns.value.len());
+        }
+        assert_eq!(
+            columns["PT15M"].timestamp,
+            vec![
+                "2023-12-31T23:00:00Z".parse().unwrap(),
+                "2023-12-31T23:15:00Z".parse().unwrap()
+            ]
+        );
+        assert_eq!(columns["PT15M"].value, vec![100.0, 101.5]);
+        assert_eq!(
+            columns["PT60M"].timestamp,
+            vec!["2024-01-01T00:00:00Z".parse().unwrap()]
+        );
+        assert_eq!(columns["PT60M"].value, vec![104.98]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_document_overlapping_periods() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T01:00Z</start>
+                    <end>2024-01-01T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>3</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>4</price.amount>
+                </Point>
+            </Period>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T00:00Z</start>
+                    <end>2024-01-01T02:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>1</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>2</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let document = try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let data = &document.series[0].data;
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T02:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(data["price.amount"], vec![Data::I64(1), Data::I64(2), Data::I64(4)]);
+        assert_eq!(data["resolution"].len(), 3);
+
+        let options = ParseOptions {
+            sort_points: false,
+            ..Default::default()
+        };
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(
+            document.series[0].data["price.amount"],
+            vec![Data::I64(3), Data::I64(4), Data::I64(1), Data::I64(2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_from_bytes_latin1() {
+        let xml_text = r#"<?xml version="1.0" encoding="ISO-8859-1"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <MktPSRType>
+                <PowerSystemResources>
+                    <name>Centrale É</name>
+                </PowerSystemResources>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>712</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+        // Encodes the document to ISO-8859-1, `É` being the only non-ASCII character.
+        let bytes: Vec<u8> = xml_text.chars().map(|c| c as u32 as u8).collect();
+
+        let data = parse_timeseries_from_bytes(
+            &bytes,
+            vec!["quantity"],
+            vec!["name"],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(data["quantity"], vec![Data::I64(712)]);
+        assert_eq!(data["name"], vec![Data::String("Centrale É".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_from_path() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let path = std::env::temp_dir().join("denki_client_test_parse_timeseries_from_path.xml");
+        std::fs::write(&path, xml_text).unwrap();
+
+        let result = parse_timeseries_from_path(
+            path.to_str().unwrap(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            result.unwrap()["price.amount"],
+            vec![Data::F64(104.98), Data::F64(105.98)]
+        );
+
+        assert!(parse_timeseries_from_path("missing.xml", vec![], vec![], "period", &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_timeseries_truncated_document() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+<TimeSeries>
+    <Period>
+        <resolution>PT60M</resolution>
+        <Point>
+            <position>1</position>
+            <price.amount>104.98</price.amo"#;
+
+        let Err(DenkiError::Parse(error)) = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period")
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(error.line, 8);
+        assert!(error.column > 0);
+        assert!(!error.message.is_empty());
+    }
+
+    #[test]
+    fn test_parse_timeseries_by_psr_type() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <mRID>1</mRID>
+            <MktPSRType>
+                <psrType>B16</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>0</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>12.5</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        <TimeSeries>
+            <mRID>2</mRID>
+            <MktPSRType>
+                <psrType>B19</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>4021</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "period", &ParseOptions::default())
+                .unwrap();
+        assert_eq!(document.series[0].psr_type.as_deref(), Some("B16"));
+        assert_eq!(document.series[0].mrid.as_deref(), Some("1"));
+
+        let columns = parse_timeseries_by_psr_type(xml_text, "quantity", "period").unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(
+            columns["B16"].timestamp,
+            vec![
+                "2023-12-31T23:00:00Z".parse().unwrap(),
+                "2024-01-01T00:00:00Z".parse().unwrap()
+            ]
+        );
+        assert_eq!(columns["B16"].value, vec![0.0, 12.5]);
+        assert_eq!(columns["B19"].value, vec![4021.0]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_fill_missing() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T02:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(data["price.amount"], vec![Data::F64(104.98), Data::F64(105.98)]);
+
+        let options = ParseOptions {
+            fill_missing: true,
+            ..Default::default()
+        };
+        let data =
+            parse_timeseries_generic_with_options(xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+            ]
+        );
+        let values = &data["price.amount"];
+        assert_eq!(values[0], Data::F64(104.98));
+        assert_eq!(values[1], Data::Null);
+        assert_eq!(values[2], Data::F64(105.98));
+    }
+
+    #[test]
+    fn test_parse_timeseries_validate_points() {
+        let period = |points: usize| {
+            let points: String = (1..=points)
+                .map(|position| format!("<Point><position>{position}</position><quantity>1</quantity></Point>"))
+                .collect();
+            format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+                <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+                <TimeSeries>
+                    <curveType>A01</curveType>
+                    <Period>
+                        <timeInterval>
+                            <start>2023-12-31T23:00Z</start>
+                            <end>2024-01-01T23:00Z</end>
+                        </timeInterval>
+                        <resolution>PT60M</resolution>
+                        {points}
+                    </Period>
+                </TimeSeries>
+                </GL_MarketDocument>"#
+            )
+        };
+        let options = ParseOptions {
+            validate_points: true,
+            ..Default::default()
+        };
+
+        let data = parse_timeseries_generic_with_options(&period(24), vec!["quantity"], vec![], "Period", &options);
+        assert_eq!(data.unwrap()["quantity"].len(), 24);
+
+        let error = parse_timeseries_generic_with_options(&period(20), vec!["quantity"], vec![], "Period", &options);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "period from 2023-12-31T23:00Z to 2024-01-01T23:00Z has 20 points, expected 24 at PT60M resolution"
+        );
+
+        let data = parse_timeseries_generic(&period(20), vec!["quantity"], vec![], "Period");
+        assert_eq!(data.unwrap()["quantity"].len(), 20);
+    }
+
+    #[test]
+    fn test_parse_timeseries_namespaced_labels() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3" xmlns:ext="urn:ext">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                    <ext:price.amount>1</ext:price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let qualified = "{urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3}price.amount";
+
+        let data =
+            parse_timeseries_generic(xml_text, vec![qualified, "{urn:ext}price.amount"], vec![], "period").unwrap();
+        assert_eq!(data[qualified], vec![Data::F64(104.98)]);
+        assert_eq!(data["{urn:ext}price.amount"], vec![Data::I64(1)]);
+
+        // A bare label matches any namespace, the last element winning.
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(data["price.amount"], vec![Data::I64(1)]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_iso_timestamps() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let options = ParseOptions {
+            iso_timestamps: true,
+            ..Default::default()
+        };
+
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(
+            document.series[0].data["timestamp"],
+            vec![
+                Data::String("2023-12-31T23:00:00Z".to_string()),
+                Data::String("2024-01-01T00:00:00Z".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unavailability() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <Unavailability_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:outagedocument:3:0">
+        <mRID>DOCUMENT</mRID>
+        <type>A80</type>
+        <unavailability_Time_Period.timeInterval>
+            <start>2024-01-01T00:00Z</start>
+            <end>2024-01-10T00:00Z</end>
+        </unavailability_Time_Period.timeInterval>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A53</businessType>
+            <production_RegisteredResource.mRID>17W100P100P0282N</production_RegisteredResource.mRID>
+            <production_RegisteredResource.name>UNIT 1</production_RegisteredResource.name>
+            <Available_Period>
+                <timeInterval>
+                    <start>2024-01-01T00:00Z</start>
+                    <end>2024-01-03T00:00Z</end>
+                </timeInterval>
+                <resolution>PT1M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>0</quantity>
+                </Point>
+            </Available_Period>
+        </TimeSeries>
+        <TimeSeries>
+            <mRID>2</mRID>
+            <businessType>A54</businessType>
+            <production_RegisteredResource.mRID>17W100P100P0283L</production_RegisteredResource.mRID>
+            <Available_Period>
+                <timeInterval>
+                    <start>2024-01-05T00:00Z</start>
+                    <end>2024-01-05T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>450.5</quantity>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <quantity>900</quantity>
+                </Point>
+            </Available_Period>
+            <Reason>
+                <code>B19</code>
+                <text>Forced outage</text>
+            </Reason>
+        </TimeSeries>
+        <Reason>
+            <code>B18</code>
+        </Reason>
+        </Unavailability_MarketDocument>
+        "#;
+
+        let outages = parse_unavailability(xml_text).unwrap();
+        assert_eq!(outages.len(), 3);
+
+        assert_eq!(outages[0].mrid.as_deref(), Some("1"));
+        assert_eq!(outages[0].business_type.as_deref(), Some("A53"));
+        assert_eq!(outages[0].asset_mrid.as_deref(), Some("17W100P100P0282N"));
+        assert_eq!(outages[0].start, "2024-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(outages[0].end, "2024-01-03T00:00:00Z".parse().unwrap());
+        assert_eq!(outages[0].quantity, 0.0);
+        assert_eq!(outages[0].reason.code.as_deref(), Some("B18"));
+
+        assert_eq!(outages[1].business_type.as_deref(), Some("A54"));
+        assert_eq!(outages[1].asset_mrid.as_deref(), Some("17W100P100P0283L"));
+        assert_eq!(outages[1].start, "2024-01-05T00:00:00Z".parse().unwrap());
+        assert_eq!(outages[1].end, "2024-01-05T02:00:00Z".parse().unwrap());
+        assert_eq!(outages[1].quantity, 450.5);
+        assert_eq!(outages[1].reason.code.as_deref(), Some("B19"));
+        assert_eq!(outages[1].reason.text.as_deref(), Some("Forced outage"));
+        assert_eq!(outages[2].start, "2024-01-05T02:00:00Z".parse().unwrap());
+        assert_eq!(outages[2].end, "2024-01-05T03:00:00Z".parse().unwrap());
+        assert_eq!(outages[2].quantity, 900.0);
+    }
+
+    #[test]
+    fn test_parse_flows() {
+        let series = |out_domain: &str, in_domain: &str, quantities: [i64; 2]| {
+            format!(
+                r#"<TimeSeries>
+                    <in_Domain.mRID codingScheme="A01">{in_domain}</in_Domain.mRID>
+                    <out_Domain.mRID codingScheme="A01">{out_domain}</out_Domain.mRID>
+                    <Period>
+                        <timeInterval>
+                            <start>2023-12-31T23:00Z</start>
+                            <end>2024-01-01T01:00Z</end>
+                        </timeInterval>
+                        <resolution>PT60M</resolution>
+                        <Point>
+                            <position>1</position>
+                            <quantity>{}</quantity>
+                        </Point>
+                        <Point>
+                            <position>2</position>
+                            <quantity>{}</quantity>
+                        </Point>
+                    </Period>
+                </TimeSeries>"#,
+                quantities[0], quantities[1]
+            )
+        };
+        let xml_text = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+            {}
+            {}
+            </Publication_MarketDocument>"#,
+            series("10YFR-RTE------C", "10Y1001A1001A82H", [1200, 300]),
+            series("10Y1001A1001A82H", "10YFR-RTE------C", [200, 800]),
+        );
+
+        let flows = parse_flows(&xml_text, "quantity", "period").unwrap();
+        assert_eq!(flows.directions.len(), 2);
+        assert_eq!(flows.directions[0].out_domain.as_deref(), Some("10YFR-RTE------C"));
+        assert_eq!(flows.directions[0].in_domain.as_deref(), Some("10Y1001A1001A82H"));
+        assert_eq!(flows.directions[0].value, vec![1200.0, 300.0]);
+        assert_eq!(flows.directions[1].out_domain.as_deref(), Some("10Y1001A1001A82H"));
+        assert_eq!(flows.directions[1].value, vec![200.0, 800.0]);
+
+        assert_eq!(flows.net.len(), 1);
+        assert_eq!(flows.net[0].out_domain.as_deref(), Some("10YFR-RTE------C"));
+        assert_eq!(flows.net[0].in_domain.as_deref(), Some("10Y1001A1001A82H"));
+        assert_eq!(
+            flows.net[0].timestamp,
+            vec![
+                "2023-12-31T23:00:00Z".parse().unwrap(),
+                "2024-01-01T00:00:00Z".parse().unwrap()
+            ]
+        );
+        assert_eq!(flows.net[0].value, vec![1000.0, -500.0]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_lenient() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>abc</price.amount>
+                </Point>
+                <Point>
+                    <position>x</position>
+                    <price.amount>1</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        assert!(try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default()
+        )
+        .is_err());
+
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(
+            document.series[0].data["price.amount"],
+            vec![Data::F64(104.98), Data::F64(105.98)]
+        );
+        assert_eq!(
+            document.warnings,
+            vec![
+                r#"skipped point 2 of TimeSeries 1: non-numeric price.amount "abc""#,
+                r#"skipped point ? of TimeSeries 1: invalid position "x""#
+            ]
+        );
+        assert_eq!(document.stats.points, 2);
+        assert_eq!(document.stats.skipped, 2);
+    }
+
+    #[test]
+    fn test_parse_timeseries_large_document() {
+        let points = 35_136;
+        let mut xml_text = String::from(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+            <TimeSeries>
+                <Period>
+                    <timeInterval>
+                        <start>2023-12-31T23:00Z</start>
+                        <end>2024-12-31T23:00Z</end>
+                    </timeInterval>
+                    <resolution>PT15M</resolution>"#,
+        );
+        for position in 1..=points {
+            xml_text.push_str(&format!(
+                "<Point><position>{position}</position><quantity>{}</quantity></Point>",
+                position % 97
+            ));
+        }
+        xml_text.push_str("</Period></TimeSeries></GL_MarketDocument>");
+
+        let data = parse_timeseries_generic(&xml_text, vec!["quantity"], vec![], "period").unwrap();
+        let start: Timestamp = "2023-12-31T23:00:00Z".parse().unwrap();
+        let expected_timestamps: Vec<Data> = (0..points)
+            .map(|i| Data::Timestamp(start.checked_add((15 * i).minutes()).unwrap()))
+            .collect();
+        let expected_quantities: Vec<Data> = (1..=points).map(|position| Data::I64(position % 97)).collect();
+        assert_eq!(data["timestamp"], expected_timestamps);
+        assert_eq!(data["quantity"], expected_quantities);
+        assert_eq!(data["resolution"].len(), points as usize);
+        assert_eq!(
+            data["timestamp"].last(),
+            Some(&Data::Timestamp("2024-12-31T22:45:00Z".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_multi() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Balancing_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:balancingdocument:4:4">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2023-12-31T23:30Z</end>
+                </timeInterval>
+                <resolution>PT15M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>10</quantity>
+                    <activation_Price.amount>116.17</activation_Price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <activation_Price.amount>111.17</activation_Price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>20</quantity>
+                    <activation_Price.amount>98.5</activation_Price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Balancing_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_multi(xml_text, &["quantity", "activation_Price.amount"], "period").unwrap();
+        assert_eq!(data.len(), 6);
+        assert_eq!(
+            data["PT15M_timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2023-12-31T23:15:00Z".parse().unwrap())
+            ]
+        );
+        assert_eq!(
+            data["PT15M_activation_Price.amount"],
+            vec![Data::F64(116.17), Data::F64(111.17)]
+        );
+        assert_eq!(data["PT15M_quantity"][0], Data::I64(10));
+        assert_eq!(data["PT15M_quantity"][1], Data::Null);
+        assert_eq!(data["PT60M_quantity"], vec![Data::I64(20)]);
+        assert_eq!(data["PT60M_activation_Price.amount"], vec![Data::F64(98.5)]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_document_periods() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <period.timeInterval>
+            <start>2023-12-31T23:00Z</start>
+            <end>2024-01-01T23:00Z</end>
+        </period.timeInterval>
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let document = try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.series[0].periods,
+            vec![Period {
+                start: "2023-12-31T23:00:00Z".parse().unwrap(),
+                end: "2024-01-01T23:00:00Z".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_interval_timestamp() {
+        let expected: Timestamp = "2023-12-31T23:00:00Z".parse().unwrap();
+        assert_eq!(interval_timestamp("2023-12-31T23:00Z").unwrap(), expected);
+        assert_eq!(interval_timestamp("2023-12-31T23:00:00Z").unwrap(), expected);
+        assert_eq!(interval_timestamp("2024-01-01T00:00+01:00").unwrap(), expected);
+        assert_eq!(interval_timestamp("2024-01-01T00:00:00+01:00").unwrap(), expected);
+        assert_eq!(interval_timestamp("2023-12-31T23:00").unwrap(), expected);
+        assert_eq!(interval_timestamp("2023-12-31T23:00:00").unwrap(), expected);
+        assert!(interval_timestamp("2023-12-31").is_err());
+    }
+
+    #[test]
+    fn test_parse_load() {
+        let xml_text = fixtures::load();
+
+        let load = parse_load(xml_text).unwrap();
+        assert_eq!(
+            load,
+            Load {
+                process_type: Some("A16".to_string()),
+                timestamp: vec![
+                    "2023-12-31T23:00:00Z".parse().unwrap(),
+                    "2024-01-01T00:00:00Z".parse().unwrap(),
+                ],
+                quantity: vec![61250, 59873],
+            }
+        );
+
+        let fractional = xml_text.replace("59873.0", "59873.5");
+        assert!(parse_load(&fractional).is_err());
+    }
+
+    #[test]
+    fn test_parse_timeseries_padded_position() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>  3  </position>
+                    <price.amount>
+                        104.98
+                    </price.amount>
+                </Point>
+                <Point>
+                    <position>04</position>
+                    <price.amount> 105 </price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T02:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(data["price.amount"], vec![Data::F64(104.98), Data::I64(105)]);
+    }
+
+    #[test]
+    fn test_parse_installed_capacity() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <type>A68</type>
+        <process.processType>A33</process.processType>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A37</businessType>
+            <inBiddingZone_Domain.mRID codingScheme="A01">10YFR-RTE------C</inBiddingZone_Domain.mRID>
+            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+            <curveType>A01</curveType>
+            <MktPSRType>
+                <psrType>B16</psrType>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2022-12-31T23:00Z</start>
+                    <end>2024-12-31T23:00Z</end>
+                </timeInterval>
+                <resolution>P1Y</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>17420</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>20150</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        <TimeSeries>
+            <mRID>2</mRID>
+            <businessType>A37</businessType>
+            <curveType>A01</curveType>
+            <MktPSRType>
+                <psrType>B16</psrType>
+                <voltage_PowerSystemResources.highVoltageLimit unit="KVT">225</voltage_PowerSystemResources.highVoltageLimit>
+            </MktPSRType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-12-31T23:00Z</end>
+                </timeInterval>
+                <resolution>P1Y</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>350</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let capacities = parse_installed_capacity(xml_text).unwrap();
+        assert_eq!(capacities.len(), 1);
+        assert_eq!(
+            capacities["B16"],
+            Columns {
+                timestamp: vec![
+                    "2022-12-31T23:00:00Z".parse().unwrap(),
+                    "2023-12-31T23:00:00Z".parse().unwrap(),
+                ],
+                value: vec![17420.0, 20500.0],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_element_names() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <timeResolution>PT60M</timeResolution>
+                <Interval>
+                    <pos>1</pos>
+                    <price.amount>104.98</price.amount>
+                </Interval>
+                <Interval>
+                    <pos>2</pos>
+                    <price.amount>105.98</price.amount>
+                </Interval>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let options = ParseOptions {
+            element_names: ElementNames {
+                point: "Interval".to_string(),
+                position: "pos".to_string(),
+                resolution: "timeResolution".to_string(),
+            },
+            ..Default::default()
+        };
+        let data =
+            parse_timeseries_generic_with_options(xml_text, vec!["price.amount"], vec![], "Period", &options).unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(data["price.amount"], vec![Data::F64(104.98), Data::F64(105.98)]);
+        assert_eq!(data["resolution"], vec![Data::String("PT60M".to_string()); 2]);
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "Period").unwrap();
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_parse_imbalance_prices() {
+        let series = |mrid: &str, direction: &str, prices: [f64; 2]| {
+            format!(
+                r#"<TimeSeries>
+                    <mRID>{mrid}</mRID>
+                    <businessType>A19</businessType>
+                    <flowDirection.direction>{direction}</flowDirection.direction>
+                    <currency_Unit.name>EUR</currency_Unit.name>
+                    <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
+                    <curveType>A01</curveType>
+                    <Period>
+                        <timeInterval>
+                            <start>2023-12-31T23:00Z</start>
+                            <end>2023-12-31T23:30Z</end>
+                        </timeInterval>
+                        <resolution>PT15M</resolution>
+                        <Point>
+                            <position>1</position>
+                            <imbalance_Price.amount>{}</imbalance_Price.amount>
+                        </Point>
+                        <Point>
+                            <position>2</position>
+                            <imbalance_Price.amount>{}</imbalance_Price.amount>
+                        </Point>
+                    </Period>
+                </TimeSeries>"#,
+                prices[0], prices[1]
+            )
+        };
+        let xml_text = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <Balancing_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:balancingdocument:4:5">
+            <type>A85</type>
+            {}
+            {}
+            </Balancing_MarketDocument>"#,
+            series("1", "A01", [120.5, 98.25]),
+            series("2", "A02", [-15.0, 42.0]),
+        );
+
+        let prices = parse_imbalance_prices(&xml_text).unwrap();
+        assert_eq!(prices.currency.as_deref(), Some("EUR"));
+        assert_eq!(prices.measure_unit.as_deref(), Some("MWH"));
+        let timestamps: Vec<Timestamp> = vec![
+            "2023-12-31T23:00:00Z".parse().unwrap(),
+            "2023-12-31T23:15:00Z".parse().unwrap(),
+        ];
+        assert_eq!(
+            prices.directions["A01"],
+            Columns {
+                timestamp: timestamps.clone(),
+                value: vec![120.5, 98.25]
+            }
+        );
+        assert_eq!(
+            prices.directions["A02"],
+            Columns {
+                timestamp: timestamps,
+                value: vec![-15.0, 42.0]
+            }
+        );
+    }
+
+    #[test]
+    fn test_data_display() {
+        assert_eq!(Data::F64(105.0).to_string(), "105");
+        assert_eq!(Data::F64(104.98).to_string(), "104.98");
+        assert_eq!(Data::F64(-0.5).to_string(), "-0.5");
+        assert_eq!(Data::I64(42).to_string(), "42");
+        assert_eq!(
+            Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()).to_string(),
+            "2023-12-31T23:00:00Z"
+        );
+        assert_eq!(Data::String("A01".to_string()).to_string(), "A01");
+        assert_eq!(Data::Null.to_string(), "");
+    }
+
+    #[test]
+    fn test_to_csv_rows() {
+        let timestamps = vec![
+            Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+            Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+            Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+        ];
+        let values = vec![Data::F64(104.98), Data::Null, Data::String("a, \"b\"".to_string())];
+        assert_eq!(
+            to_csv_rows(&timestamps, &values),
+            vec![
+                "2023-12-31T23:00:00Z,104.98",
+                "2024-01-01T00:00:00Z,",
+                "2024-01-01T01:00:00Z,\"a, \"\"b\"\"\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_for_each_point() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T02:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>106.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let mut points = Vec::new();
+        for_each_point(xml_text, "price.amount", "Period", |resolution, timestamp, value| {
+            points.push((resolution.to_string(), timestamp, value.clone()));
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(points.len(), 3);
+        assert_eq!(
+            points[0],
+            (
+                "PT60M".to_string(),
+                "2023-12-31T23:00:00Z".parse().unwrap(),
+                Data::F64(104.98)
+            )
+        );
+
+        let mut count = 0;
+        for_each_point(xml_text, "price.amount", "Period", |_, _, _| {
+            count += 1;
+            if count == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_merge_timeseries() {
+        let result = |hours: [i64; 2], prices: [f64; 2]| {
+            let start: Timestamp = "2023-12-31T23:00:00Z".parse().unwrap();
+            HashMap::from([
+                (
+                    "timestamp".to_string(),
+                    hours
+                        .iter()
+                        .map(|hour| Data::Timestamp(start.checked_add(hour.hours()).unwrap()))
+                        .collect(),
+                ),
+                ("price.amount".to_string(), prices.map(Data::F64).to_vec()),
+                ("resolution".to_string(), vec![Data::String("PT60M".to_string()); 2]),
+            ])
+        };
+        let mut second = result([1, 2], [106.0, 107.0]);
+        second.insert("currency".to_string(), vec![Data::String("EUR".to_string()); 2]);
+
+        let merged = merge_timeseries(vec![second, result([0, 1], [104.0, 105.0])]).unwrap();
+        assert_eq!(
+            merged["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(
+            merged["price.amount"],
+            vec![Data::F64(104.0), Data::F64(105.0), Data::F64(107.0)]
+        );
+        assert_eq!(
+            merged["currency"],
+            vec![Data::Null, Data::Null, Data::String("EUR".to_string())]
+        );
+        assert_eq!(merged["resolution"].len(), 3);
+    }
+
+    #[test]
+    fn test_merge_timeseries_options() {
+        let xml_text = fixtures::day_ahead_price();
+        for options in [
+            ParseOptions::default(),
+            ParseOptions {
+                iso_timestamps: true,
+                ..Default::default()
+            },
+            ParseOptions {
+                output_tz: Some(TimeZone::get("Europe/Paris").unwrap()),
+                ..Default::default()
+            },
+            ParseOptions {
+                output_tz: Some(TimeZone::get("Europe/Paris").unwrap()),
+                iso_timestamps: true,
+                ..Default::default()
+            },
+        ] {
+            let result =
+                parse_timeseries_generic_with_options(xml_text, vec!["price.amount"], vec![], "Period", &options)
+                    .unwrap();
+            let merged = merge_timeseries(vec![result.clone(), result.clone()]).unwrap();
+            assert_eq!(merged, result, "{options:?}");
+        }
+
+        let result = |timestamps: [&str; 2], values: [i64; 2]| {
+            HashMap::from([
+                (
+                    "timestamp".to_string(),
+                    timestamps.map(|t| Data::String(t.to_string())).to_vec(),
+                ),
+                ("quantity".to_string(), values.map(Data::I64).to_vec()),
+            ])
+        };
+        let merged = merge_timeseries(vec![
+            result(["2024-01-01T01:00:00Z", "2024-01-01T02:00:00Z"], [2, 3]),
+            result(["2024-01-01T00:00:00Z", "2024-01-01T01:00:00Z"], [1, 4]),
+        ])
+        .unwrap();
+        assert_eq!(merged["quantity"], vec![Data::I64(1), Data::I64(4), Data::I64(3)]);
+
+        let without_timestamp = HashMap::from([("quantity".to_string(), vec![Data::I64(1), Data::I64(2)])]);
+        assert_eq!(
+            merge_timeseries(vec![without_timestamp]).unwrap_err().to_string(),
+            "cannot merge a result without a timestamp column"
+        );
+        let mut null_timestamp = result(["2024-01-01T00:00:00Z", "2024-01-01T01:00:00Z"], [1, 2]);
+        null_timestamp.get_mut("timestamp").unwrap()[1] = Data::Null;
+        assert_eq!(
+            merge_timeseries(vec![null_timestamp]).unwrap_err().to_string(),
+            "cannot sort points on timestamp Null"
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_offset_start() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T00:00+01:00</start>
+                    <end>2024-01-01T02:00:00-01:00</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let document = try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec![],
+            "Period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.series[0].data["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(
+            document.series[0].periods,
+            vec![Period {
+                start: "2023-12-31T23:00:00Z".parse().unwrap(),
+                end: "2024-01-01T03:00:00Z".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_generation_forecast() {
+        let series = |psr_type: &str, start: &str, quantities: &[i64]| {
+            let points: String = quantities
+                .iter()
+                .enumerate()
+                .map(|(i, quantity)| {
+                    format!(
+                        "<Point><position>{}</position><quantity>{quantity}</quantity></Point>",
+                        i + 1
+                    )
+                })
+                .collect();
+            format!(
+                r#"<TimeSeries>
+                    <businessType>A93</businessType>
+                    <curveType>A01</curveType>
+                    <MktPSRType>
+                        <psrType>{psr_type}</psrType>
+                    </MktPSRType>
+                    <Period>
+                        <timeInterval>
+                            <start>{start}</start>
+                            <end>2024-01-01T02:00Z</end>
+                        </timeInterval>
+                        <resolution>PT60M</resolution>
+                        {points}
+                    </Period>
+                </TimeSeries>"#
+            )
+        };
+        let xml_text = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+            <type>A69</type>
+            <process.processType>A01</process.processType>
+            {}
+            {}
+            </GL_MarketDocument>"#,
+            series("B16", "2024-01-01T00:00Z", &[120, 340]),
+            series("B19", "2023-12-31T23:00Z", &[5100, 5230, 5360]),
+        );
+        let timestamps: Vec<Timestamp> = vec![
+            "2023-12-31T23:00:00Z".parse().unwrap(),
+            "2024-01-01T00:00:00Z".parse().unwrap(),
+            "2024-01-01T01:00:00Z".parse().unwrap(),
+        ];
+
+        let forecast = parse_generation_forecast(&xml_text, false).unwrap();
+        assert_eq!(forecast.process_type.as_deref(), Some("A01"));
+        assert_eq!(forecast.series["B16"].timestamp, timestamps[1..]);
+        assert_eq!(forecast.series["B19"].value, vec![5100.0, 5230.0, 5360.0]);
+        assert!(forecast.timestamp.is_empty());
+        assert!(forecast.aligned.is_empty());
+
+        let forecast = parse_generation_forecast(&xml_text, true).unwrap();
+        assert_eq!(forecast.timestamp, timestamps);
+        assert_eq!(
+            forecast.aligned["B16"],
+            vec![Data::Null, Data::F64(120.0), Data::F64(340.0)]
+        );
+        assert_eq!(
+            forecast.aligned["B19"],
+            vec![Data::F64(5100.0), Data::F64(5230.0), Data::F64(5360.0)]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_reasons() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <mRID>1</mRID>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T00:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>61250</quantity>
+                </Point>
+            </Period>
+            <Reason>
+                <code>B47</code>
+                <text>Partial data</text>
+            </Reason>
+        </TimeSeries>
+        <TimeSeries>
+            <mRID>2</mRID>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], "Period", &ParseOptions::default())
+                .unwrap();
+        assert_eq!(
+            document.series[0].reasons,
+            vec![Reason {
+                code: Some("B47".to_string()),
+                text: Some("Partial data".to_string()),
+            }]
+        );
+        assert!(document.series[1].reasons.is_empty());
+        assert_eq!(document.series[0].data["quantity"], vec![Data::I64(61250)]);
+    }
+
+    #[test]
+    fn test_data_approx_eq() {
+        let a = Data::F64(104.98);
+        let b = Data::F64(104.98 + 1e-12);
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-9));
+        assert!(!a.approx_eq(&Data::F64(104.99), 1e-9));
+        assert!(Data::I64(105).approx_eq(&Data::F64(105.0), 1e-9));
+        assert!(!Data::F64(f64::NAN).approx_eq(&Data::F64(f64::NAN), 1e-9));
+        assert!(Data::Null.approx_eq(&Data::Null, 1e-9));
+        assert!(!Data::String("105".to_string()).approx_eq(&Data::I64(105), 1e-9));
+    }
+
+    #[test]
+    fn test_parse_scheduled_exchanges() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <type>A09</type>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A06</businessType>
+            <in_Domain.mRID codingScheme="A01">10YES-REE------0</in_Domain.mRID>
+            <out_Domain.mRID codingScheme="A01">10YFR-RTE------C</out_Domain.mRID>
+            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>1800</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>1650</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let exchanges = parse_scheduled_exchanges(xml_text).unwrap();
+        assert_eq!(
+            exchanges,
+            vec![Flow {
+                out_domain: Some("10YFR-RTE------C".to_string()),
+                in_domain: Some("10YES-REE------0".to_string()),
+                timestamp: vec![
+                    "2023-12-31T23:00:00Z".parse().unwrap(),
+                    "2024-01-01T00:00:00Z".parse().unwrap(),
+                ],
+                value: vec![1800.0, 1650.0],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_price_series() {
+        let period = |resolution: &str, positions: [i64; 2]| {
+            format!(
+                r#"<Period>
+                    <timeInterval>
+                        <start>2023-12-31T23:00Z</start>
+                        <end>2024-01-01T01:00Z</end>
+                    </timeInterval>
+                    <resolution>{resolution}</resolution>
+                    <Point>
+                        <position>{}</position>
+                        <price.amount>105.98</price.amount>
+                        <quantity>20</quantity>
+                    </Point>
+                    <Point>
+                        <position>{}</position>
+                        <price.amount>104.98</price.amount>
+                        <quantity>10</quantity>
+                    </Point>
+                </Period>"#,
+                positions[0], positions[1]
+            )
+        };
+        let document = |periods: String| {
+            format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+                <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+                <TimeSeries>{periods}</TimeSeries>
+                </Publication_MarketDocument>"#
+            )
+        };
+        let first: Timestamp = "2023-12-31T23:00:00Z".parse().unwrap();
+        let second: Timestamp = "2024-01-01T00:00:00Z".parse().unwrap();
+
+        let xml_text = document(period("PT60M", [2, 1]));
+        assert_eq!(
+            parse_price_series(&xml_text).unwrap(),
+            vec![(first, 104.98), (second, 105.98)]
+        );
+        assert_eq!(
+            parse_quantity_series(&xml_text).unwrap(),
+            vec![(first, 10.0), (second, 20.0)]
+        );
+
+        let xml_text = document(period("PT60M", [2, 1]) + &period("PT15M", [1, 2]));
+        let error = parse_price_series(&xml_text).unwrap_err();
+        assert!(error.to_string().contains("PT15M, PT60M"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_timeseries_periods_resolutions() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <curveType>A03</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2023-12-31T23:30Z</end>
+                </timeInterval>
+                <resolution>PT15M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T00:00Z</start>
+                    <end>2024-01-01T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>106.98</price.amount>
+                </Point>
+            </Period>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T03:00Z</start>
+                    <end>2024-01-01T04:00Z</end>
+                </timeInterval>
+                <Point>
+                    <position>1</position>
+                    <price.amount>107.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(
+            data["timestamp"],
+            vec![
+                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                Data::Timestamp("2023-12-31T23:15:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T01:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T02:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(
+            data["resolution"],
+            vec![
+                Data::String("PT15M".to_string()),
+                Data::String("PT15M".to_string()),
+                Data::String("PT60M".to_string()),
+                Data::String("PT60M".to_string()),
+            ]
+        );
+        // The last period declares no resolution: its point is dropped rather than stepped by the previous one.
+        assert_eq!(
+            data["price.amount"],
+            vec![
+                Data::F64(104.98),
+                Data::F64(104.98),
+                Data::F64(105.98),
+                Data::F64(106.98)
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json_round_trip() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <mRID>bf4445f7e6e04c849b7e0830b906fbde</mRID>
+        <createdDateTime>2025-05-17T21:13:31Z</createdDateTime>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <in_Domain.mRID codingScheme="A01">10YFR-RTE------C</in_Domain.mRID>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T02:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>105</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let options = ParseOptions {
+            fill_missing: true,
+            ..Default::default()
+        };
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "Period", &options).unwrap();
+
+        let json = super::to_json(&document).unwrap();
+        assert!(json.contains(r#"{"Timestamp":"2023-12-31T23:00:00Z"}"#), "{json}");
+        let deserialized: super::Document = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, document);
+        assert_eq!(
+            deserialized.series[0].data["price.amount"],
+            vec![Data::F64(104.98), Data::Null, Data::I64(105)]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_document_interval() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2024-01-01T11:00Z</start>
+                    <end>2024-01-01T13:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <period.timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
+                </period.timeInterval>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        <period.timeInterval>
+            <start>2023-12-31T23:00Z</start>
+            <end>2024-01-01T23:00Z</end>
+        </period.timeInterval>
+        </Publication_MarketDocument>
+        "#;
+
+        let document = try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec![],
+            "Period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.series[0].data["timestamp"],
+            vec![
+                Data::Timestamp("2024-01-01T11:00:00Z".parse().unwrap()),
+                Data::Timestamp("2024-01-01T12:00:00Z".parse().unwrap()),
+            ]
+        );
+        assert_eq!(
+            document.series[0].periods,
+            vec![Period {
+                start: "2024-01-01T11:00:00Z".parse().unwrap(),
+                end: "2024-01-01T13:00:00Z".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_multi_with_keys() {
+        let series = |price: f64| {
+            format!(
+                r#"<TimeSeries>
+                    <Period>
+                        <timeInterval>
+                            <start>2023-12-31T23:00Z</start>
+                            <end>2024-01-01T00:00Z</end>
+                        </timeInterval>
+                        <resolution>PT60M</resolution>
+                        <Point>
+                            <position>1</position>
+                            <price.amount>{price}</price.amount>
+                        </Point>
+                    </Period>
+                </TimeSeries>"#
+            )
+        };
+        let xml_text = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+            {}
+            {}
+            </Publication_MarketDocument>"#,
+            series(104.98),
+            series(-5.5),
+        );
+        let timestamp = Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap());
+
+        let data = parse_timeseries_multi(&xml_text, &["price.amount"], "Period").unwrap();
+        assert_eq!(data["PT60M_price.amount"], vec![Data::F64(104.98), Data::F64(-5.5)]);
+
+        let keys = KeyFormat {
+            separator: "/".to_string(),
+            ..Default::default()
+        };
+        let data = parse_timeseries_multi_with_keys(&xml_text, &["price.amount"], "Period", &keys).unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data["PT60M/timestamp"], vec![timestamp.clone(), timestamp.clone()]);
+
+        let keys = KeyFormat {
+            series_index: true,
+            ..Default::default()
+        };
+        let data = parse_timeseries_multi_with_keys(&xml_text, &["price.amount"], "Period", &keys).unwrap();
+        assert_eq!(data.len(), 4);
+        assert_eq!(data["0_PT60M_timestamp"], vec![timestamp.clone()]);
+        assert_eq!(data["0_PT60M_price.amount"], vec![Data::F64(104.98)]);
+        assert_eq!(data["1_PT60M_timestamp"], vec![timestamp]);
+        assert_eq!(data["1_PT60M_price.amount"], vec![Data::F64(-5.5)]);
+    }
+
+    #[test]
+    fn test_parse_timeseries_output_tz() {
+        let options = ParseOptions {
+            output_tz: Some(TimeZone::get("Europe/Paris").unwrap()),
+            ..Default::default()
+        };
+
+        let document = try_parse_timeseries_document(
+            fixtures::day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &options,
+        )
+        .unwrap();
+        let Data::Zoned(first) = &document.series[0].data["timestamp"][0] else {
+            panic!("expected a zoned timestamp");
+        };
+        assert_eq!(first.to_string(), "2024-01-01T00:00:00+01:00[Europe/Paris]");
+        assert_eq!(first.timestamp(), "2023-12-31T23:00:00Z".parse::<Timestamp>().unwrap());
+
+        let options = ParseOptions {
+            iso_timestamps: true,
+            ..options
+        };
+        let document = try_parse_timeseries_document(
+            fixtures::day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            document.series[0].data["timestamp"][0],
+            Data::String("2024-01-01T00:00:00+01:00[Europe/Paris]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_output_tz_fall_back() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2024-10-26T23:00Z</start>
+                    <end>2024-10-27T02:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>80.5</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>75.1</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>70.2</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let options = ParseOptions {
+            output_tz: Some(TimeZone::get("Europe/Paris").unwrap()),
+            ..Default::default()
+        };
+
+        let document =
+            try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        let series = &document.series[0];
+        let local: Vec<String> = series.data["timestamp"]
+            .iter()
+            .map(|timestamp| match timestamp {
+                Data::Zoned(zoned) => zoned.datetime().to_string(),
+                _ => panic!("expected a zoned timestamp"),
+            })
+            .collect();
+        assert_eq!(
+            local,
+            vec!["2024-10-27T01:00:00", "2024-10-27T02:00:00", "2024-10-27T02:00:00"]
+        );
+        assert_eq!(
+            series.data["utc_offset"],
+            vec![Data::I64(7200), Data::I64(7200), Data::I64(3600)]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_html_page() {
+        let html = "\n<!DOCTYPE html>\n<html><head><title>502 Bad Gateway</title></head><body></body></html>";
+
+        let Err(DenkiError::Parse(err)) = parse_timeseries_generic(html, vec!["price.amount"], vec![], "period") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(
+            err.message,
+            "response is not an ENTSO-E XML document but an HTML page: <!DOCTYPE html>"
+        );
+        let err = parse_timeseries_generic("<HTML><body>Error</body></HTML>", vec![], vec![], "period").unwrap_err();
+        assert!(err.to_string().contains("HTML page: <HTML><body>Error</body></HTML>"));
+    }
+
+    #[test]
+    fn test_parse_timeseries_validate_positions() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point><position>1</position><quantity>1</quantity></Point>
+                <Point><position>2</position><quantity>2</quantity></Point>
+                <Point><position>4</position><quantity>4</quantity></Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>"#;
+        let options = ParseOptions {
+            validate_points: true,
+            ..Default::default()
+        };
+
+        let error = parse_timeseries_generic_with_options(xml_text, vec!["quantity"], vec![], "Period", &options);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "missing position 3 (2024-01-01T01:00:00Z) before position 4 of TimeSeries 1"
+        );
+
+        let options = ParseOptions {
+            fill_missing: true,
+            ..options
+        };
+        let data =
+            parse_timeseries_generic_with_options(xml_text, vec!["quantity"], vec![], "Period", &options).unwrap();
+        assert_eq!(data["quantity"][2], Data::Null);
+    }
+
+    #[test]
+    fn test_parse_timeseries_multidoc() {
+        let price = fixtures::day_ahead_price();
+        let without_declaration = &price[price.find("?>").unwrap() + 2..];
+        let xml_text = format!("{price}\n{price}{without_declaration}");
+        let options = ParseOptions::default();
+
+        assert!(try_parse_timeseries_document(
+            &xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default()
+        )
+        .is_err());
+        let document = parse_timeseries_multidoc(&xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        let single =
+            try_parse_timeseries_document(price, vec!["price.amount"], vec![], "period", &ParseOptions::default())
+                .unwrap();
+        assert_eq!(document.series.len(), 3);
+        assert_eq!(document.stats.points, 3 * single.stats.points);
+        assert_eq!(document.series[2], single.series[0]);
+        assert_eq!(document.metadata, single.metadata);
+    }
+
+    #[test]
+    fn test_parse_reservoir() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <type>A72</type>
+        <process.processType>A16</process.processType>
+        <TimeSeries>
+            <businessType>B31</businessType>
+            <inBiddingZone_Domain.mRID codingScheme="A01">10YFR-RTE------C</inBiddingZone_Domain.mRID>
+            <quantity_Measure_Unit.name>MWH</quantity_Measure_Unit.name>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-24T23:00Z</start>
+                    <end>2024-01-07T23:00Z</end>
+                </timeInterval>
+                <resolution>P7D</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>2522703</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>2491502.5</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </GL_MarketDocument>
+        "#;
+
+        let reservoir = parse_reservoir(xml_text).unwrap();
+        assert_eq!(
+            reservoir,
+            Reservoir {
+                domain: Some("10YFR-RTE------C".to_string()),
+                measure_unit: Some("MWH".to_string()),
+                timestamp: vec![
+                    "2023-12-24T23:00:00Z".parse().unwrap(),
+                    "2023-12-31T23:00:00Z".parse().unwrap(),
+                ],
+                stored_energy: vec![2522703.0, 2491502.5],
+            }
+        );
+    }
+
+    /// Records every log event, as tests share the global logger.
+    struct CapturingLogger(std::sync::Mutex<Vec<(log::Level, String)>>);
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger(std::sync::Mutex::new(vec![]));
+
+    #[test]
+    fn test_parse_timeseries_lenient_logs() {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(log::LevelFilter::Debug);
+        }
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <mRID>logged-document</mRID>
+        <TimeSeries>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>logged-price</price.amount>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+
+        try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        let records = LOGGER.0.lock().unwrap();
+        assert!(records
+            .iter()
+            .any(|(level, message)| *level == log::Level::Warn && message.contains("\"logged-price\"")));
+        assert!(records.iter().any(|(level, message)| *level == log::Level::Debug
+            && message == "document logged-document: 1 series, 1 periods, 1 points, 1 skipped points"));
+    }
+
+    #[test]
+    fn test_parse_timeseries_a03_until_period_end() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <curveType>A03</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T05:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let document = try_parse_timeseries_document(
+            xml_text,
+            vec!["price.amount"],
+            vec![],
+            "Period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        let data = &document.series[0].data;
+        assert_eq!(
+            data["timestamp"],
+            (0..6)
+                .map(|hour| Data::Timestamp("2023-12-31T23:00:00Z".parse::<Timestamp>().unwrap() + hour.hours()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            data["price.amount"],
+            vec![
+                Data::F64(104.98),
+                Data::F64(104.98),
+                Data::F64(105.98),
+                Data::F64(105.98),
+                Data::F64(105.98),
+                Data::F64(105.98),
+            ]
+        );
+        assert_eq!(document.stats.points, 6);
+    }
+
+    #[test]
+    fn test_parse_net_transfer_capacity() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <type>A61</type>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A27</businessType>
+            <in_Domain.mRID codingScheme="A01">10YBE----------2</in_Domain.mRID>
+            <out_Domain.mRID codingScheme="A01">10YFR-RTE------C</out_Domain.mRID>
+            <contract_MarketAgreement.type>A01</contract_MarketAgreement.type>
+            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>2300</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>2150</quantity>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+
+        let capacities = parse_net_transfer_capacity(xml_text).unwrap();
+        assert_eq!(
+            capacities,
+            vec![TransferCapacity {
+                out_domain: Some("10YFR-RTE------C".to_string()),
+                in_domain: Some("10YBE----------2".to_string()),
+                contract_agreement_type: Some("A01".to_string()),
+                timestamp: vec![
+                    "2023-12-31T23:00:00Z".parse().unwrap(),
+                    "2024-01-01T00:00:00Z".parse().unwrap(),
+                ],
+                value: vec![2300.0, 2150.0],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_process_type() {
+        let document = try_parse_timeseries_document(
+            fixtures::load(),
+            vec!["quantity"],
+            vec![],
+            "Period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(document.series[0].process_type.as_deref(), Some("A16"));
+        assert_eq!(
+            document.series[0].process_type.as_deref().and_then(process_type_name),
+            Some("Realised")
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_multi_positions() {
+        let keys = KeyFormat {
+            positions: true,
+            ..Default::default()
+        };
+
+        let data =
+            parse_timeseries_multi_with_keys(fixtures::day_ahead_price(), &["price.amount"], "period", &keys).unwrap();
+        let mut positions = vec![Data::I64(1), Data::I64(2)];
+        positions.extend(vec![Data::Null; 22]);
+        assert_eq!(data["PT60M_position"], positions);
+        assert_eq!(data["PT60M_price.amount"], day_ahead_prices());
+
+        let xml_text = fixtures::day_ahead_price().replace("<position>2</position>", "<position>3</position>");
+        let options = ParseOptions {
+            keep_positions: true,
+            ..Default::default()
+        };
+        let document =
+            try_parse_timeseries_document(&xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(
+            document.series[0].data["position"][..4],
+            [Data::I64(1), Data::Null, Data::I64(3), Data::Null]
+        );
+    }
+
+    #[test]
+    fn test_parse_activated_energy() {
+        let series = |direction: &str, resolution: &str, quantities: &[f64]| {
+            let points: String = quantities
+                .iter()
+                .enumerate()
+                .map(|(i, quantity)| {
+                    format!(
+                        "<Point><position>{}</position><quantity>{quantity}</quantity></Point>",
+                        i + 1
+                    )
+                })
+                .collect();
+            format!(
+                r#"<TimeSeries>
+                    <businessType>A96</businessType>
+                    <flowDirection.direction>{direction}</flowDirection.direction>
+                    <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
+                    <curveType>A01</curveType>
+                    <Period>
+                        <timeInterval>
+                            <start>2024-01-01T00:00Z</start>
+                            <end>2024-01-01T00:45Z</end>
+                        </timeInterval>
+                        <resolution>{resolution}</resolution>
+                        {points}
+                    </Period>
+                </TimeSeries>"#
+            )
+        };
+        let xml_text = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <Balancing_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:balancingdocument:4:4">
+            <type>A83</type>
+            {}
+            {}
+            </Balancing_MarketDocument>"#,
+            series("A01", "PT15M", &[120.5, 98.0, 143.25]),
+            series("A02", "PT1M", &[12.0, 8.5]),
+        );
+
+        let activations = parse_activated_energy(&xml_text).unwrap();
+        assert_eq!(activations.len(), 2);
+        assert_eq!(
+            activations[0],
+            ActivatedEnergy {
+                flow_direction: Some("A01".to_string()),
+                business_type: Some("A96".to_string()),
+                measure_unit: Some("MAW".to_string()),
+                timestamp: vec![
+                    "2024-01-01T00:00:00Z".parse().unwrap(),
+                    "2024-01-01T00:15:00Z".parse().unwrap(),
+                    "2024-01-01T00:30:00Z".parse().unwrap(),
+                ],
+                value: vec![120.5, 98.0, 143.25],
+            }
+        );
+        assert_eq!(activations[1].flow_direction.as_deref(), Some("A02"));
+        assert_eq!(
+            activations[1].timestamp,
+            vec![
+                "2024-01-01T00:00:00Z".parse::<Timestamp>().unwrap(),
+                "2024-01-01T00:01:00Z".parse().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_decimal_comma() {
+        let xml_text = fixtures::day_ahead_price().replace("104.98", "104,98");
+
+        let document = try_parse_timeseries_document(
+            &xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.series[0].data["price.amount"][0],
+            Data::String("104,98".to_string())
+        );
+
+        let options = ParseOptions {
+            decimal_comma: true,
+            ..Default::default()
+        };
+        let document =
+            try_parse_timeseries_document(&xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(document.series[0].data["price.amount"], day_ahead_prices());
+    }
+
+    #[test]
+    fn test_parse_total_load_vintages() {
+        let forecast = fixtures::load()
+            .replace("<process.processType>A16", "<process.processType>A01")
+            .replace("61250", "60500");
+        let xml_text = format!("{forecast}\n{}", fixtures::load());
+
+        let load = parse_total_load_forecast(&xml_text).unwrap();
+        assert_eq!(load.process_type.as_deref(), Some("A01"));
+        assert_eq!(load.quantity, vec![60500, 59873]);
+
+        let load = parse_actual_total_load(&xml_text).unwrap();
+        assert_eq!(load.process_type.as_deref(), Some("A16"));
+        assert_eq!(load.quantity, vec![61250, 59873]);
+        assert_eq!(load.timestamp.len(), 2);
+
+        assert!(parse_total_load_forecast(fixtures::load()).unwrap().quantity.is_empty());
+    }
+
+    #[test]
+    fn test_parse_timeseries_records() {
+        let records = parse_timeseries_records(fixtures::day_ahead_price(), "price.amount", "period").unwrap();
+
+        let record = |timestamp: &str, value: f64| {
+            HashMap::from([
+                ("timestamp".to_string(), Data::Timestamp(timestamp.parse().unwrap())),
+                ("resolution".to_string(), Data::String("PT60M".to_string())),
+                ("value".to_string(), Data::F64(value)),
+            ])
+        };
+        assert_eq!(records.len(), 24);
+        assert_eq!(
+            records[..3],
+            [
+                record("2023-12-31T23:00:00Z", 104.98),
+                record("2024-01-01T00:00:00Z", 105.98),
+                record("2024-01-01T01:00:00Z", 105.98)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_position_base() {
+        let xml_text = fixtures::day_ahead_price()
+            .replace("<position>1</position>", "<position>0</position>")
+            .replace("<position>2</position>", "<position>1</position>");
+        let options = ParseOptions {
+            position_base: 0,
+            keep_positions: true,
+            ..Default::default()
+        };
+
+        let data =
+            parse_timeseries_generic_with_options(&xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(data["timestamp"], grid("2023-12-31T23:00:00Z", 60, 24));
+        assert_eq!(data["position"][..3], [Data::I64(0), Data::I64(1), Data::Null]);
+
+        let data = parse_timeseries_generic(&xml_text, vec!["price.amount"], vec![], "period").unwrap();
+        assert_eq!(
+            data["timestamp"][0],
+            Data::Timestamp("2023-12-31T22:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_to_csv() {
+        let csv = parse_timeseries_to_csv(fixtures::day_ahead_price(), "price.amount", "period").unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 25);
+        assert_eq!(
+            lines[..3],
+            [
+                "timestamp,value",
+                "2023-12-31T23:00:00Z,104.98",
+                "2024-01-01T00:00:00Z,105.98"
+            ]
+        );
+        assert_eq!(lines[24], "2024-01-01T22:00:00Z,105.98");
+
+        let record = |timestamp: &str, resolution: &str, value: f64| {
+            HashMap::from([
+                ("timestamp".to_string(), Data::Timestamp(timestamp.parse().unwrap())),
+                ("resolution".to_string(), Data::String(resolution.to_string())),
+                ("value".to_string(), Data::F64(value)),
+            ])
+        };
+        let mut csv = Vec::new();
+        to_csv(
+            &[
+                record("2023-12-31T23:00:00Z", "PT60M", 104.98),
+                record("2023-12-31T23:00:00Z", "PT15M", 104.5),
+            ],
+            &mut csv,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "timestamp,resolution,value\n2023-12-31T23:00:00Z,PT60M,104.98\n2023-12-31T23:00:00Z,PT15M,104.5\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_auto() {
+        assert_eq!(
+            document_type(fixtures::day_ahead_price()).unwrap().as_deref(),
+            Some("A44")
+        );
+        assert_eq!(
+            parse_auto(fixtures::day_ahead_price()).unwrap(),
+            parse_timeseries_generic(fixtures::day_ahead_price(), vec!["price.amount"], vec![], "Period").unwrap()
+        );
+        assert_eq!(parse_auto(fixtures::load()).unwrap()["quantity"].len(), 2);
+
+        let xml_text = fixtures::day_ahead_price().replace("<type>A44</type>", "<type>B45</type>");
+        assert_eq!(
+            parse_auto(&xml_text).unwrap_err().to_string(),
+            "unsupported document type B45, label and period name must be given explicitly"
+        );
+        let xml_text = fixtures::day_ahead_price().replace("<type>A44</type>", "");
+        assert_eq!(document_type(&xml_text).unwrap(), None);
+        assert_eq!(
+            parse_auto(&xml_text).unwrap_err().to_string(),
+            "document has no type element"
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_period_name_case() {
+        let xml_text = |curve_type: &str| {
+            format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <curveType>{curve_type}</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T02:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#
+            )
+        };
+        let options = ParseOptions {
+            validate_points: true,
+            ..Default::default()
+        };
+
+        for period_name in ["Period", "period", "ns:Period"] {
+            // The end of the period holds the last point of the A03 curve until the end of its `timeInterval`.
+            let data = parse_timeseries_generic(&xml_text("A03"), vec!["price.amount"], vec![], period_name).unwrap();
+            assert_eq!(data["timestamp"], grid("2023-12-31T23:00:00Z", 60, 3), "{period_name}");
+            assert_eq!(data["price.amount"], vec![Data::F64(104.98); 3], "{period_name}");
+
+            let error = parse_timeseries_generic_with_options(
+                &xml_text("A01"),
+                vec!["price.amount"],
+                vec![],
+                period_name,
+                &options,
+            )
+            .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "period from 2023-12-31T23:00Z to 2024-01-01T02:00Z has 1 points, expected 3 at PT60M resolution",
+                "{period_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timeseries_nth() {
+        let second = fixtures::day_ahead_price()
+            .split("<TimeSeries>")
+            .nth(1)
+            .unwrap()
+            .split("</TimeSeries>")
+            .next()
+            .unwrap()
+            .replace("<mRID>1</mRID>", "<mRID>2</mRID>")
+            .replace("104.98", "99.5");
+        let xml_text = fixtures::day_ahead_price().replace(
+            "</TimeSeries>",
+            &format!("</TimeSeries>\n<TimeSeries>{second}</TimeSeries>"),
+        );
+
+        let series = parse_timeseries_nth(&xml_text, "price.amount", "Period", 1).unwrap();
+        assert_eq!(series.mrid.as_deref(), Some("2"));
+        assert_eq!(series.data["price.amount"][..2], [Data::F64(99.5), Data::F64(105.98)]);
+        assert_eq!(series.data["timestamp"], grid("2023-12-31T23:00:00Z", 60, 24));
+
+        let series = parse_timeseries_nth(&xml_text, "price.amount", "Period", 0).unwrap();
+        assert_eq!(series.data["price.amount"], day_ahead_prices());
+
+        assert_eq!(
+            parse_timeseries_nth(&xml_text, "price.amount", "Period", 2)
+                .unwrap_err()
+                .to_string(),
+            "TimeSeries index 2 out of range, document has 2 series"
+        );
+    }
+
+    #[test]
+    fn test_denki_error() {
+        assert_eq!(DenkiError::from_status(200), None);
+        assert_eq!(DenkiError::from_status(401), Some(DenkiError::Auth));
+        assert_eq!(DenkiError::from_status(429), Some(DenkiError::RateLimited));
+        assert_eq!(DenkiError::from_status(503), Some(DenkiError::Http(503)));
+        assert_eq!(
+            DenkiError::from_status(503).unwrap().to_string(),
+            "ENTSO-E responded with status 503"
+        );
+
+        let xml_text = fixtures::day_ahead_price().replace("<type>A44</type>", "<type>B45</type>");
+        assert!(matches!(parse_auto(&xml_text), Err(DenkiError::Unsupported(_))));
+
+        let error = DenkiError::from(ParseError {
+            line: 2,
+            column: 3,
+            message: "unexpected end of stream".to_string(),
+        });
+        assert_eq!(error.to_string(), "2:3: unexpected end of stream");
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_parse_timeseries_resampled() {
+        let period = |start: &str, end: &str, resolution: &str, values: &[f64]| {
+            let points: String = values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    format!(
+                        "<Point><position>{}</position><quantity>{value}</quantity></Point>",
+                        i + 1
+                    )
+                })
+                .collect();
+            format!(
+                "<Period><timeInterval><start>{start}</start><end>{end}</end></timeInterval>\
+                <resolution>{resolution}</resolution>{points}</Period>"
+            )
+        };
+        let xml_text = |periods: String| {
+            format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
+        <TimeSeries><curveType>A01</curveType>{periods}</TimeSeries>
+        </GL_MarketDocument>"#
+            )
+        };
+        let xml = xml_text(
+            period("2023-12-31T23:00Z", "2024-01-01T00:00Z", "PT60M", &[100.0])
+                + &period(
+                    "2024-01-01T00:00Z",
+                    "2024-01-01T01:00Z",
+                    "PT15M",
+                    &[10.0, 20.0, 30.0, 40.0],
+                ),
+        );
+
+        let columns = parse_timeseries_resampled(&xml, "quantity", "Period", Aggregation::Mean).unwrap();
+        assert_eq!(
+            columns,
+            Columns {
+                timestamp: vec![
+                    "2023-12-31T23:00:00Z".parse().unwrap(),
+                    "2024-01-01T00:00:00Z".parse().unwrap()
+                ],
+                value: vec![100.0, 25.0],
+            }
+        );
+        let columns = parse_timeseries_resampled(&xml, "quantity", "Period", Aggregation::Sum).unwrap();
+        assert_eq!(columns.value, vec![100.0, 100.0]);
+
+        let xml = xml_text(
+            period("2023-12-31T23:00Z", "2024-01-01T00:00Z", "PT60M", &[100.0])
+                + &period("2024-01-01T00:00Z", "2024-01-01T00:45Z", "PT45M", &[10.0]),
+        );
+        assert_eq!(
+            parse_timeseries_resampled(&xml, "quantity", "Period", Aggregation::Mean)
+                .unwrap_err()
+                .to_string(),
+            "cannot resample PT45M onto PT60M, which is not an integer multiple of it"
+        );
+
+        for (resolution, message) in [
+            ("PT0M", "resolution must be positive: PT0M"),
+            ("PT0.5S", "cannot resample resolution PT0.5S shorter than a second"),
+        ] {
+            let xml = xml_text(period("2023-12-31T23:00Z", "2023-12-31T23:00:01Z", resolution, &[10.0]));
+            assert_eq!(
+                parse_timeseries_resampled(&xml, "quantity", "Period", Aggregation::Mean)
+                    .unwrap_err()
+                    .to_string(),
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timeseries_by_psr_type_consumption() {
+        let pumping = fixtures::generation()
+            .replacen("inBiddingZone_Domain", "outBiddingZone_Domain", 2)
+            .replace("<psrType>B16</psrType>", "<psrType>B10</psrType>");
+        let generation = fixtures::generation().replace("<psrType>B16</psrType>", "<psrType>B10</psrType>");
+        let xml_text = format!("{generation}\n{pumping}");
+
+        let document =
+            parse_timeseries_multidoc(&xml_text, vec!["quantity"], vec![], "Period", &Default::default()).unwrap();
+        let pumped_storage = document
+            .series
+            .iter()
+            .filter(|series| series.psr_type.as_deref() == Some("B10"))
+            .collect::<Vec<_>>();
+        assert_eq!(pumped_storage.len(), 2);
+        assert_eq!(pumped_storage[0].energy_flow.as_deref(), Some("generation"));
+        assert_eq!(pumped_storage[0].in_domain.as_deref(), Some("10YFR-RTE------C"));
+        assert_eq!(pumped_storage[1].energy_flow.as_deref(), Some("consumption"));
+        assert_eq!(pumped_storage[1].in_domain, None);
+        assert_eq!(pumped_storage[1].out_domain.as_deref(), Some("10YFR-RTE------C"));
+
+        let columns = parse_timeseries_by_psr_type(&pumping, "quantity", "Period").unwrap();
+        let mut keys = columns.keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["B10_consumption", "B19"]);
+    }
+
+    #[test]
+    fn test_try_parse_timeseries_document() {
+        let parse = |xml_text: &str| {
+            try_parse_timeseries_document(xml_text, vec!["price.amount"], vec![], "Period", &Default::default())
+        };
+
+        let xml_text = fixtures::day_ahead_price();
+        let Err(DenkiError::Parse(error)) = parse(&xml_text[..xml_text.len() - 40]) else {
+            panic!("expected a parse error");
+        };
+        assert!(error.line > 1, "{error}");
+
+        let xml_text = fixtures::day_ahead_price().replace("<position>2</position>", "<position>two</position>");
+        assert_eq!(
+            parse(&xml_text),
+            Err(DenkiError::Invalid(r#"invalid position "two""#.to_string()))
+        );
+
+        let xml_text =
+            fixtures::day_ahead_price().replace("<resolution>PT60M</resolution>", "<resolution>1h</resolution>");
+        assert_eq!(
+            parse(&xml_text),
+            Err(DenkiError::Invalid("unsupported resolution: 1h".to_string()))
+        );
+
+        let acknowledgement = r#"<?xml version="1.0" encoding="utf-8"?>
+<Acknowledgement_MarketDocument><Reason><code>999</code><text>No matching data found</text></Reason></Acknowledgement_MarketDocument>"#;
+        assert!(matches!(parse(acknowledgement), Err(DenkiError::Acknowledgement(_))));
+
+        let error = parse_timeseries_from_path(
+            "fixtures/missing.xml",
+            vec!["price.amount"],
+            vec![],
+            "Period",
+            &Default::default(),
+        );
+        assert!(matches!(error, Err(DenkiError::Io(_))), "{error:?}");
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_parse_timeseries_document_anyhow() {
+        assert_eq!(
+            super::parse_timeseries_document(fixtures::day_ahead_price(), vec!["price.amount"], vec![], "Period")
+                .unwrap(),
+            try_parse_timeseries_document(
+                fixtures::day_ahead_price(),
+                vec!["price.amount"],
+                vec![],
+                "Period",
+                &Default::default()
+            )
+            .unwrap()
+        );
+
+        let xml_text = fixtures::day_ahead_price();
+        let error =
+            super::parse_timeseries_document(&xml_text[..xml_text.len() - 40], vec!["price.amount"], vec![], "Period")
+                .unwrap_err();
+        assert!(matches!(DenkiError::from(error), DenkiError::Parse(_)));
+    }
+
+    #[test]
+    fn test_parse_timeseries_available_period() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Capacity_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-2:capacitydocument:8:0">
+        <type>A31</type>
+        <TimeSeries>
+            <mRID>1</mRID>
+            <businessType>A31</businessType>
+            <in_Domain.mRID codingScheme="A01">10YFR-RTE------C</in_Domain.mRID>
+            <out_Domain.mRID codingScheme="A01">10YCH-SWISSGRIDZ</out_Domain.mRID>
+            <curveType>A01</curveType>
+            <Available_Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T01:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>1</position>
+                    <quantity>1200</quantity>
+                </Point>
+                <Point>
+                    <position>2</position>
+                    <quantity>1100</quantity>
+                </Point>
+            </Available_Period>
+            <Available_Period>
+                <timeInterval>
+                    <start>2024-01-01T01:00Z</start>
+                    <end>2024-01-01T02:00Z</end>
+                </timeInterval>
+                <resolution>PT30M</resolution>
+                <Point>
+                    <position>2</position>
+                    <quantity>900</quantity>
+                </Point>
+            </Available_Period>
+        </TimeSeries>
+        </Capacity_MarketDocument>
+        "#;
+
+        for period_name in ["Available_Period", "available_period"] {
+            let document = try_parse_timeseries_document(
+                xml_text,
+                vec!["quantity"],
+                vec![],
+                period_name,
+                &ParseOptions::default(),
+            )
+            .unwrap();
+            let series = &document.series[0];
+            assert_eq!(series.periods.len(), 2);
+            // The second period steps from its own start and resolution.
+            assert_eq!(
+                series.data["timestamp"],
+                vec![
+                    Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+                    Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+                    Data::Timestamp("2024-01-01T01:30:00Z".parse().unwrap()),
+                ]
+            );
+            assert_eq!(
+                series.data["quantity"],
+                vec![Data::I64(1200), Data::I64(1100), Data::I64(900)]
+            );
+            assert_eq!(
+                series.data["resolution"],
+                vec![
+                    Data::String("PT60M".to_string()),
+                    Data::String("PT60M".to_string()),
+                    Data::String("PT30M".to_string()),
+                ]
+            );
+
+            // The points of each `Available_Period` are validated, the second one missing its first position.
+            let options = ParseOptions {
+                validate_points: true,
+                ..Default::default()
+            };
+            let error =
+                try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], period_name, &options).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "missing position 1 (2024-01-01T01:00:00Z) before position 2 of TimeSeries 1"
+            );
+            let options = ParseOptions {
+                fill_missing: true,
+                ..options
+            };
+            let document =
+                try_parse_timeseries_document(xml_text, vec!["quantity"], vec![], period_name, &options).unwrap();
+            assert_eq!(
+                document.series[0].data["quantity"],
+                vec![Data::I64(1200), Data::I64(1100), Data::Null, Data::I64(900)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timeseries_zero_resolution() {
+        for resolution in ["PT0M", "-PT60M"] {
+            let xml_text = fixtures::day_ahead_price().replace(
+                "<resolution>PT60M</resolution>",
+                &format!("<resolution>{resolution}</resolution>"),
+            );
+            assert_eq!(
+                parse_timeseries_generic(&xml_text, vec!["price.amount"], vec![], "Period")
+                    .unwrap_err()
+                    .to_string(),
+                format!("resolution must be positive: {resolution}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timeseries_generic_point_metadata() {
+        let period = |start: &str, points: &str| {
+            format!(
+                "<Period><timeInterval><start>{start}</start><end>2024-01-02T00:00Z</end></timeInterval>\
+                <resolution>PT60M</resolution>{points}</Period>"
+            )
+        };
+        let xml_text = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+        <Balancing_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:balancingdocument:4:4">
+        <TimeSeries>
+            <businessType>A19</businessType>
+            {}
+        </TimeSeries>
+        <TimeSeries>
+            {}
+            <businessType>A20</businessType>
+            <mktPSRType.psrType>A04</mktPSRType.psrType>
+        </TimeSeries>
+        </Balancing_MarketDocument>"#,
+            period(
+                "2024-01-01T00:00Z",
+                "<Point><position>1</position><imbalance_Price.amount>1</imbalance_Price.amount>\
+                <imbalance_Price.category>A04</imbalance_Price.category></Point>\
+                <Point><position>2</position><imbalance_Price.amount>2</imbalance_Price.amount>\
+                <imbalance_Price.category>A05</imbalance_Price.category></Point>"
+            ),
+            period(
+                "2024-01-01T02:00Z",
+                "<Point><position>1</position><imbalance_Price.amount>3</imbalance_Price.amount></Point>"
+            ),
+        );
+
+        let data = parse_timeseries_generic(
+            &xml_text,
+            vec!["imbalance_Price.amount"],
+            vec![
+                "imbalance_Price.category",
+                "businessType",
+                "mktPSRType.psrType",
+                "psrType",
+            ],
+            "Period",
+        )
+        .unwrap();
+        let strings = |values: [&str; 2]| values.map(|value| Data::String(value.to_string()));
+        assert_eq!(data["timestamp"].len(), 3);
+        // Read per point, along with the values of the series read before it.
+        assert_eq!(data["imbalance_Price.category"][..2], strings(["A04", "A05"]));
+        assert_eq!(data["imbalance_Price.category"][2], Data::Null);
+        // Following the period, the values of the second series apply to its point.
+        assert_eq!(data["businessType"][1..], strings(["A19", "A20"]));
+        assert_eq!(
+            data["mktPSRType.psrType"],
+            vec![Data::Null, Data::Null, Data::String("A04".to_string())]
+        );
+        assert!(!data.contains_key("psrType"));
+    }
+
+    #[test]
+    fn test_parse_timeseries_fill_missing_period_bounds() {
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
+            <curveType>A01</curveType>
+            <Period>
+                <timeInterval>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T03:00Z</end>
+                </timeInterval>
+                <resolution>PT60M</resolution>
+                <Point>
+                    <position>2</position>
+                    <price.amount>104.98</price.amount>
+                </Point>
+                <Point>
+                    <position>3</position>
+                    <price.amount>105.98</price.amount>
+                </Point>
+            </Period>
+        </TimeSeries>
+        </Publication_MarketDocument>
+        "#;
+        let options = ParseOptions {
+            fill_missing: true,
+            validate_points: true,
+            ..Default::default()
+        };
+
+        let data =
+            parse_timeseries_generic_with_options(xml_text, vec!["price.amount"], vec![], "Period", &options).unwrap();
+        assert_eq!(data["timestamp"], grid("2023-12-31T23:00:00Z", 60, 4));
+        assert_eq!(
+            data["price.amount"],
+            vec![Data::Null, Data::F64(104.98), Data::F64(105.98), Data::Null]
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_position_cap() {
+        // Without the end of its period, a corrupt position is only bounded by `MAX_PERIOD_POINTS`.
+        let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+        <TimeSeries>
             <curveType>A03</curveType>
             <Period>
                 <timeInterval>
                     <start>2023-12-31T23:00Z</start>
-                    <end>2024-01-01T23:00Z</end>
                 </timeInterval>
-                <resolution>PT60M</resolution>
+                <resolution>PT1M</resolution>
                 <Point>
                     <position>1</position>
                     <price.amount>104.98</price.amount>
                 </Point>
                 <Point>
-                    <position>2</position>
+                    <position>2000000</position>
                     <price.amount>105.98</price.amount>
                 </Point>
             </Period>
         </TimeSeries>
-        </publication_marketdocument>
+        </Publication_MarketDocument>
         "#;
 
-        let result = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period");
-        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
-
-        let data = result.unwrap();
-        assert!(data.contains_key("timestamp"), "{}", format!("Keys: {:?}", data.keys()));
-        assert!(
-            data.contains_key("price.amount"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert!(
-            data.contains_key("resolution"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert_eq!(
-            data["timestamp"],
-            vec![
-                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
-                Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
-            ]
-        );
-        assert_eq!(data["price.amount"], vec![Data::F64(104.98), Data::F64(105.98)]);
         assert_eq!(
-            data["resolution"],
-            vec![Data::String("PT60M".to_string()), Data::String("PT60M".to_string())]
+            parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "Period")
+                .unwrap_err()
+                .to_string(),
+            "positions 2 to 1999999 exceed the 1000000 points a period may hold"
         );
     }
 
     #[test]
-    fn test_parse_timeseries_balancy_energy_price() {
+    fn test_parse_timeseries_position_beyond_period() {
         let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
-        <Balancing_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:balancingdocument:4:1">
-        <mRID>05e317126b4f46f3bb4af1f0314ca0e7</mRID>
-        <revisionNumber>1</revisionNumber>
-        <type>A84</type>
-        <process.processType>A16</process.processType>
-        <sender_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</sender_MarketParticipant.mRID>
-        <sender_MarketParticipant.marketRole.type>A32</sender_MarketParticipant.marketRole.type>
-        <receiver_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</receiver_MarketParticipant.mRID>
-        <receiver_MarketParticipant.marketRole.type>A33</receiver_MarketParticipant.marketRole.type>
-        <createdDateTime>2024-09-24T15:45:51Z</createdDateTime>
-        <area_Domain.mRID codingScheme="A01">10YBE----------2</area_Domain.mRID>
-        <period.timeInterval>
-            <start>2023-09-03T22:00Z</start>
-            <end>2023-09-04T22:00Z</end>
-        </period.timeInterval>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
         <TimeSeries>
-            <mRID>1</mRID>
-            <businessType>A96</businessType>
-            <original_MarketProduct.marketProductType>A02</original_MarketProduct.marketProductType>
-            <mktPSRType.psrType>A03</mktPSRType.psrType>
-            <flowDirection.direction>A01</flowDirection.direction>
-            <currency_Unit.name>EUR</currency_Unit.name>
-            <price_Measure_Unit.name>MWH</price_Measure_Unit.name>
             <curveType>A03</curveType>
             <Period>
                 <timeInterval>
-                    <start>2023-09-03T22:00Z</start>
-                    <end>2023-09-04T22:00Z</end>
+                    <start>2023-12-31T23:00Z</start>
+                    <end>2024-01-01T23:00Z</end>
                 </timeInterval>
-                <resolution>PT15M</resolution>
+                <resolution>PT1M</resolution>
                 <Point>
                     <position>1</position>
-                    <activation_Price.amount>116.17</activation_Price.amount>
-                    <imbalance_Price.category>A06</imbalance_Price.category>
+                    <price.amount>104.98</price.amount>
                 </Point>
                 <Point>
-                    <position>3</position>
-                    <activation_Price.amount>111.17</activation_Price.amount>
-                    <imbalance_Price.category>A06</imbalance_Price.category>
+                    <position>3000000</position>
+                    <price.amount>105.98</price.amount>
                 </Point>
             </Period>
         </TimeSeries>
-        </Balancing_MarketDocument>
+        </Publication_MarketDocument>
         "#;
 
-        let result = parse_timeseries_generic(
-            xml_text,
-            vec!["activation_Price.amount"],
-            vec!["flowDirection.direction", "businessType"],
-            "period",
-        );
-        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
-
-        let data = result.unwrap();
-        assert!(data.contains_key("timestamp"), "{}", format!("Keys: {:?}", data.keys()));
-        assert!(
-            data.contains_key("activation_Price.amount"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert!(
-            data.contains_key("flowDirection.direction"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert!(
-            data.contains_key("businessType"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert!(
-            data.contains_key("resolution"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert_eq!(
-            data["timestamp"],
-            vec![
-                Data::Timestamp("2023-09-03T22:00:00Z".parse().unwrap()),
-                Data::Timestamp("2023-09-03T22:30:00Z".parse().unwrap()),
-            ]
-        );
-        assert_eq!(
-            data["activation_Price.amount"],
-            vec![Data::F64(116.17), Data::F64(111.17)]
-        );
         assert_eq!(
-            data["resolution"],
-            vec![Data::String("PT15M".to_string()), Data::String("PT15M".to_string())]
+            parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "Period")
+                .unwrap_err()
+                .to_string(),
+            "position 3000000 of TimeSeries 1 is beyond the 1440 points of its period from 2023-12-31T23:00Z to \
+             2024-01-01T23:00Z"
         );
+
+        let xml_text = xml_text.replace("<position>3000000</position>", "<position>1440</position>");
+        let data = parse_timeseries_generic(&xml_text, vec!["price.amount"], vec![], "Period").unwrap();
+        assert_eq!(data["price.amount"].len(), 1440);
     }
 
     #[test]
-    fn test_parse_timeseries_installed_capacity_per_production_type() {
-        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
-        <GL_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:generationloaddocument:3:0">
-        <mRID>7c96d034d7e34768995d82b8602f4800</mRID>
-        <revisionNumber>1</revisionNumber>
-        <type>A68</type>
-        <process.processType>A33</process.processType>
-        <sender_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</sender_MarketParticipant.mRID>
-        <sender_MarketParticipant.marketRole.type>A32</sender_MarketParticipant.marketRole.type>
-        <receiver_MarketParticipant.mRID codingScheme="A01">10X1001A1001A450</receiver_MarketParticipant.mRID>
-        <receiver_MarketParticipant.marketRole.type>A33</receiver_MarketParticipant.marketRole.type>
-        <createdDateTime>2023-08-18T16:15:35Z</createdDateTime>
-        <time_Period.timeInterval>
-            <start>2022-12-31T23:00Z</start>
-            <end>2023-12-31T23:00Z</end>
-        </time_Period.timeInterval>
+    fn test_parse_timeseries_fill_missing_position_beyond_period() {
+        let xml_text = |end: &str, position: i64| {
+            format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+        <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
         <TimeSeries>
-            <mRID>1</mRID>
-            <businessType>A37</businessType>
-            <objectAggregation>A08</objectAggregation>
-            <inBiddingZone_Domain.mRID codingScheme="A01">10YBE----------2</inBiddingZone_Domain.mRID>
-            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
             <curveType>A01</curveType>
-            <MktPSRType>
-                <psrType>B01</psrType>
-            </MktPSRType>
             <Period>
                 <timeInterval>
-                    <start>2022-12-31T23:00Z</start>
-                    <end>2023-12-31T23:00Z</end>
+                    <start>2023-12-31T23:00Z</start>{end}
                 </timeInterval>
-                <resolution>P1Y</resolution>
+                <resolution>PT1M</resolution>
                 <Point>
-                    <position>1</position>
-                    <quantity>712</quantity>
+                    <position>{position}</position>
+                    <price.amount>104.98</price.amount>
                 </Point>
             </Period>
         </TimeSeries>
+        </Publication_MarketDocument>"#
+            )
+        };
+        let options = ParseOptions {
+            fill_missing: true,
+            ..Default::default()
+        };
+        let parse = |xml_text: &str| {
+            parse_timeseries_generic_with_options(xml_text, vec!["price.amount"], vec![], "Period", &options)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            parse(&xml_text("<end>2024-01-01T23:00Z</end>", 3000000)),
+            "position 3000000 of TimeSeries 1 is beyond the 1440 points of its period from 2023-12-31T23:00Z to \
+             2024-01-01T23:00Z"
+        );
+        assert_eq!(
+            parse(&xml_text("", 3000000)),
+            "positions 1 to 2999999 exceed the 1000000 points a period may hold"
+        );
+    }
+
+    #[test]
+    fn test_parse_timeseries_document_header() {
+        let document = try_parse_timeseries_document(
+            fixtures::day_ahead_price(),
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.metadata.mrid.as_deref(),
+            Some("bf4445f7e6e04c849b7e0830b906fbde")
+        );
+        assert_eq!(
+            document.metadata.created_datetime,
+            Some("2025-05-17T21:13:31Z".parse().unwrap())
+        );
+
+        // Pretty-printed text is trimmed.
+        let xml_text = fixtures::day_ahead_price().replace(
+            "<createddatetime>2025-05-17T21:13:31Z</createddatetime>",
+            "<createddatetime>\n    2025-05-17T21:13:31Z\n</createddatetime>",
+        );
+        let document = try_parse_timeseries_document(
+            &xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            document.metadata.created_datetime,
+            Some("2025-05-17T21:13:31Z".parse().unwrap())
+        );
 
+        let xml_text = fixtures::day_ahead_price().replace("2025-05-17T21:13:31Z", "yesterday");
+        let error = try_parse_timeseries_document(
+            &xml_text,
+            vec!["price.amount"],
+            vec![],
+            "period",
+            &ParseOptions::default(),
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().starts_with("invalid createdDateTime \"yesterday\""),
+            "{error}"
+        );
+        let options = ParseOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let document =
+            try_parse_timeseries_document(&xml_text, vec!["price.amount"], vec![], "period", &options).unwrap();
+        assert_eq!(document.metadata.created_datetime, None);
+        assert_eq!(document.warnings.len(), 1);
+        assert!(
+            document.warnings[0].starts_with("skipped createdDateTime \"yesterday\""),
+            "{:?}",
+            document.warnings
+        );
+        assert_eq!(document.stats.skipped, 0);
+        assert_eq!(document.stats.points, 24);
+    }
+
+    #[test]
+    fn test_parse_unavailability_indented_points() {
+        let xml_text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <Unavailability_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-6:outagedocument:3:0">
         <TimeSeries>
-            <mRID>2</mRID>
-            <businessType>A37</businessType>
-            <objectAggregation>A08</objectAggregation>
-            <inBiddingZone_Domain.mRID codingScheme="A01">10YBE----------2</inBiddingZone_Domain.mRID>
-            <quantity_Measure_Unit.name>MAW</quantity_Measure_Unit.name>
-            <curveType>A01</curveType>
-            <MktPSRType>
-                <psrType>B04</psrType>
-            </MktPSRType>
-            <Period>
+            <mRID>1</mRID>
+            <Available_Period>
                 <timeInterval>
-                    <start>2022-12-31T23:00Z</start>
-                    <end>2023-12-31T23:00Z</end>
+                    <start>2024-01-05T00:00Z</start>
+                    <end>2024-01-05T03:00Z</end>
                 </timeInterval>
-                <resolution>P1Y</resolution>
+                <resolution>PT60M</resolution>
                 <Point>
-                    <position>1</position>
-                    <quantity>6915</quantity>
+                    <position>
+                        1
+                    </position>
+                    <quantity>
+                        450.5
+                    </quantity>
                 </Point>
                 <Point>
-                    <position>2</position>
-                    <quantity>6913</quantity>
+                    <position> 3 </position>
+                    <quantity> 900 </quantity>
                 </Point>
-            </Period>
+            </Available_Period>
         </TimeSeries>
-        </GL_MarketDocument>
+        </Unavailability_MarketDocument>
         "#;
 
-        let result = parse_timeseries_generic(
-            xml_text,
-            vec!["quantity"],
-            vec!["quantity_Measure_Unit.name", "psrType"],
-            "period",
-        );
-        assert!(result.is_ok(), "{}", format!("Error: {:?}", result.err().unwrap()));
+        let outages = parse_unavailability(xml_text).unwrap();
+        assert_eq!(outages.len(), 2);
+        assert_eq!(outages[0].start, "2024-01-05T00:00:00Z".parse().unwrap());
+        assert_eq!(outages[0].end, "2024-01-05T02:00:00Z".parse().unwrap());
+        assert_eq!(outages[0].quantity, 450.5);
+        assert_eq!(outages[1].start, "2024-01-05T02:00:00Z".parse().unwrap());
+        assert_eq!(outages[1].quantity, 900.0);
 
-        let data = result.unwrap();
-        assert!(data.contains_key("timestamp"), "{}", format!("Keys: {:?}", data.keys()));
-        assert!(data.contains_key("quantity"), "{}", format!("Keys: {:?}", data.keys()));
-        assert!(
-            data.contains_key("quantity_Measure_Unit.name"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
-        );
-        assert!(data.contains_key("psrType"), "{}", format!("Keys: {:?}", data.keys()));
-        assert!(
-            data.contains_key("resolution"),
-            "{}",
-            format!("Keys: {:?}", data.keys())
+        let error = parse_unavailability(&xml_text.replace("<quantity> 900 </quantity>", "<quantity>n/a</quantity>"))
+            .unwrap_err();
+        assert_eq!(error.to_string(), r#"invalid quantity "n/a""#);
+    }
+
+    impl From<xml::reader::Error> for ParseError {
+        fn from(e: xml::reader::Error) -> Self {
+            let position = e.position();
+            Self {
+                line: position.row + 1,
+                column: position.column + 1,
+                message: e.msg().to_string(),
+            }
+        }
+    }
+
+    /// Markup of a document read with `xml-rs`, the parser preceding `quick-xml`, to check that both read documents
+    /// alike.
+    struct XmlRsReader<R: Read> {
+        events: Events<BufReader<R>>,
+        name: OwnedName,
+    }
+
+    impl<R: Read> XmlRsReader<R> {
+        fn new(reader: R) -> Result<Self, DenkiError> {
+            let mut reader = BufReader::new(reader);
+            reject_html(reader.fill_buf()?)?;
+            Ok(Self {
+                events: EventReader::new(reader).into_iter(),
+                name: OwnedName::local(""),
+            })
+        }
+    }
+
+    impl<R: Read> MarkupReader for XmlRsReader<R> {
+        fn next_markup(&mut self, text: &mut String) -> Result<Markup<'_>, ParseError> {
+            loop {
+                match self.events.next() {
+                    Some(Ok(XmlEvent::StartElement { name, attributes, .. })) => {
+                        self.name = name;
+                        let coding_scheme = attributes
+                            .into_iter()
+                            .find(|attribute| attribute.name.local_name.eq_ignore_ascii_case("codingScheme"))
+                            .map(|attribute| attribute.value);
+                        let name = Name {
+                            namespace: self.name.namespace.as_deref(),
+                            local_name: &self.name.local_name,
+                        };
+                        return Ok(Markup::Start { name, coding_scheme });
+                    }
+                    Some(Ok(XmlEvent::EndElement { name })) => {
+                        self.name = name;
+                        return Ok(Markup::End(Name {
+                            namespace: self.name.namespace.as_deref(),
+                            local_name: &self.name.local_name,
+                        }));
+                    }
+                    Some(Ok(XmlEvent::Characters(characters))) => text.push_str(&characters),
+                    Some(Ok(XmlEvent::EndDocument)) | None => return Ok(Markup::Eof),
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                }
+            }
+        }
+    }
+
+    /// A document parsed both into a `Document` and flattened, with the failures as strings.
+    type Parsed = (Result<Document, String>, Result<HashMap<String, Vec<Data>>, String>);
+
+    /// Parses the document read by `reader`.
+    fn parse_markup<M: MarkupReader>(
+        reader: impl Fn() -> Result<M, DenkiError>,
+        labels: &[&str],
+        metadata: &[&str],
+        options: &ParseOptions,
+    ) -> Parsed {
+        let document = reader().and_then(|reader| {
+            parse_timeseries_events(
+                reader,
+                labels.to_vec(),
+                metadata.to_vec(),
+                "Period",
+                options,
+                |_, series, timestamp, values, resolution| {
+                    series.push_point(timestamp, values, resolution);
+                    ControlFlow::Continue(())
+                },
+            )
+        });
+        let data = reader().and_then(|reader| {
+            parse_timeseries_generic_reader(reader, labels.to_vec(), metadata.to_vec(), "Period", options)
+        });
+        (document.map_err(|e| e.to_string()), data.map_err(|e| e.to_string()))
+    }
+
+    /// Parses `xml_text` with `quick-xml` and with the `xml-rs` reference.
+    fn parse_with_both(xml_text: &[u8], labels: &[&str], metadata: &[&str], options: &ParseOptions) -> [Parsed; 2] {
+        [
+            parse_markup(|| document_reader(xml_text), labels, metadata, options),
+            parse_markup(|| XmlRsReader::new(xml_text), labels, metadata, options),
+        ]
+    }
+
+    #[test]
+    fn test_parse_timeseries_matches_xml_rs() {
+        let options = ParseOptions::default();
+        let keep_positions = ParseOptions {
+            keep_positions: true,
+            output_tz: Some(TimeZone::get("Europe/Paris").unwrap()),
+            ..Default::default()
+        };
+        let price = fixtures::day_ahead_price();
+        let generation = fixtures::generation();
+        let latin1: Vec<u8> = price
+            .replacen("utf-8", "ISO-8859-1", 1)
+            .replacen("<mRID>1</mRID>", "<mRID>Échange</mRID>", 1)
+            .chars()
+            .map(|character| u8::try_from(u32::from(character)).unwrap())
+            .collect();
+        let check = |xml_text: &[u8], labels: &[&str], metadata: &[&str], options: &ParseOptions| {
+            let [quick_xml, xml_rs] = parse_with_both(xml_text, labels, metadata, options);
+            assert!(quick_xml.0.is_ok(), "{:?}", quick_xml.0);
+            assert_eq!(quick_xml, xml_rs);
+            quick_xml.0.unwrap()
+        };
+
+        check(
+            price.as_bytes(),
+            &["price.amount"],
+            &["currency_Unit.name", "mRID"],
+            &options,
         );
-        assert_eq!(
-            data["timestamp"],
-            vec![
-                Data::Timestamp("2022-12-31T23:00:00Z".parse().unwrap()),
-                Data::Timestamp("2022-12-31T23:00:00Z".parse().unwrap()),
-                Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
-            ]
+        let namespaced = "{urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3}price.amount";
+        check(price.as_bytes(), &[namespaced], &[], &options);
+        check(
+            price.replace('\n', "\r\n").as_bytes(),
+            &["price.amount"],
+            &["mRID"],
+            &keep_positions,
         );
-        assert_eq!(
-            data["quantity"],
-            vec![Data::ISize(712), Data::ISize(6915), Data::ISize(6913)]
+        check(fixtures::load().as_bytes(), &["quantity"], &["businessType"], &options);
+        check(
+            generation.as_bytes(),
+            &["quantity"],
+            &["psrType", "inBiddingZone_Domain.mRID"],
+            &keep_positions,
         );
-        assert_eq!(
-            data["resolution"],
-            vec![
-                Data::String("P1Y".to_string()),
-                Data::String("P1Y".to_string()),
-                Data::String("P1Y".to_string())
-            ]
+        let reformatted = generation
+            .replace("<mRID>1</mRID>", "<mRID>A &amp; B&#x20;&#67;<!-- comment -->D</mRID>")
+            .replace("<quantity>", "<quantity>\n    ")
+            .replace("<businessType>A01</businessType>", "<businessType/>");
+        let document = check(
+            reformatted.as_bytes(),
+            &["quantity"],
+            &["mRID", "businessType"],
+            &options,
         );
+        assert_eq!(document.series[0].mrid.as_deref(), Some("A & B CD"));
+        let document = check(&latin1, &["price.amount"], &["mRID"], &options);
+        assert_eq!(document.series[0].mrid.as_deref(), Some("Échange"));
+    }
+
+    #[test]
+    fn test_parse_timeseries_fails_like_xml_rs() {
+        let price = fixtures::day_ahead_price();
+        let without_declaration = &price[price.find("?>").unwrap() + 2..];
+        let malformed = [
+            String::new(),
+            "   ".to_string(),
+            price[..price.len() - 40].to_string(),
+            price[..price.find("</publication_marketdocument>").unwrap()].to_string(),
+            price.replacen("</mRID>", "</mrid>", 1),
+            price.replacen("<mRID>", "<mRID>&unknown;", 1),
+            price.replacen("codingScheme=\"A01\"", "codingScheme=\"A01\" codingScheme=\"A02\"", 1),
+            format!("{price}{price}"),
+            format!("{price}{without_declaration}"),
+            format!("{price}trailing text"),
+            "<!DOCTYPE html><html></html>".to_string(),
+        ];
+
+        for xml_text in malformed {
+            let [quick_xml, xml_rs] = parse_with_both(xml_text.as_bytes(), &["price.amount"], &[], &Default::default());
+            assert_eq!(
+                (quick_xml.0.is_err(), quick_xml.1.is_err()),
+                (xml_rs.0.is_err(), xml_rs.1.is_err()),
+                "{xml_text:?}: {quick_xml:?} against {xml_rs:?}"
+            );
+        }
     }
 }
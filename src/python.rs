@@ -0,0 +1,482 @@
+use std::{collections::HashMap, ops::ControlFlow};
+
+use jiff::tz::TimeZone;
+use pyo3::{
+    exceptions::{PyOSError, PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBool, PyDict},
+};
+
+use crate::{codes, parsers};
+
+/// Exception `name` of `denki_client.exceptions`, where the whole hierarchy of the client is defined.
+fn client_exception<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    py.import("denki_client.exceptions")?.getattr(name)
+}
+
+fn denki_parse_error(py: Python<'_>, e: &parsers::ParseError) -> PyResult<PyErr> {
+    let value = client_exception(py, "DenkiParseError")?.call1((e.to_string(),))?;
+    value.setattr("line", e.line)?;
+    value.setattr("column", e.column)?;
+    value.setattr("message", &e.message)?;
+    Ok(PyErr::from_value(value))
+}
+
+/// `NoMatchingDataError` of the client, with the `code` and `text` of the `reason`, an acknowledgement holding no data
+/// just as a response of the client found without any.
+fn no_matching_data_error(py: Python<'_>, e: &parsers::DenkiError, reason: &parsers::Reason) -> PyResult<PyErr> {
+    let value = client_exception(py, "NoMatchingDataError")?.call1((e.to_string(),))?;
+    value.setattr("code", &reason.code)?;
+    value.setattr("text", &reason.text)?;
+    Ok(PyErr::from_value(value))
+}
+
+fn unsupported_error(py: Python<'_>, message: &str) -> PyResult<PyErr> {
+    let value = client_exception(py, "DenkiUnsupportedError")?.call1((message,))?;
+    Ok(PyErr::from_value(value))
+}
+
+/// `EntsoeResponseError` subclass `name` of the client for a bare `status`, as raised for a response instead.
+fn response_error(py: Python<'_>, name: &str, status: u16, e: &parsers::DenkiError) -> PyResult<PyErr> {
+    let value = client_exception(py, name)?.call_method1("from_status", (status, e.to_string()))?;
+    Ok(PyErr::from_value(value))
+}
+
+fn denki_error(e: &parsers::DenkiError) -> PyErr {
+    Python::with_gil(|py| {
+        match e {
+            parsers::DenkiError::Parse(e) => denki_parse_error(py, e),
+            parsers::DenkiError::Unsupported(message) => unsupported_error(py, message),
+            parsers::DenkiError::Invalid(message) => Ok(PyErr::new::<PyValueError, _>(message.clone())),
+            parsers::DenkiError::Io(message) => Ok(PyErr::new::<PyOSError, _>(message.clone())),
+            parsers::DenkiError::Acknowledgement(reason) => no_matching_data_error(py, e, reason),
+            parsers::DenkiError::Http(status) => response_error(py, "DenkiHttpError", *status, e),
+            parsers::DenkiError::Auth => response_error(py, "DenkiAuthError", 401, e),
+            parsers::DenkiError::RateLimited => response_error(py, "DenkiRateLimitedError", 429, e),
+        }
+        .unwrap_or_else(|import_err| import_err)
+    })
+}
+
+fn to_py_err(e: parsers::DenkiError) -> PyErr {
+    denki_error(&e)
+}
+
+/// `ParseOptions` of the keyword arguments shared by the parsers taking options, as listed by `ParseOptions` in
+/// `_core.pyi`, failing on any other one.
+fn parse_options(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<parsers::ParseOptions> {
+    let mut options = parsers::ParseOptions::default();
+    let time_zone = |tz: &str| TimeZone::get(tz).map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()));
+    for (key, value) in kwargs.into_iter().flatten() {
+        match key.extract::<String>()?.as_str() {
+            "tz" => {
+                if let Some(tz) = value.extract::<Option<String>>()? {
+                    options.tz = time_zone(&tz)?;
+                }
+            }
+            "sort_points" => options.sort_points = value.extract()?,
+            "fill_missing" => options.fill_missing = value.extract()?,
+            "validate_points" => options.validate_points = value.extract()?,
+            "iso_timestamps" => options.iso_timestamps = value.extract()?,
+            "output_tz" => {
+                options.output_tz = value
+                    .extract::<Option<String>>()?
+                    .as_deref()
+                    .map(time_zone)
+                    .transpose()?
+            }
+            "lenient" => options.lenient = value.extract()?,
+            "decimal_comma" => options.decimal_comma = value.extract()?,
+            "position_base" => options.position_base = value.extract()?,
+            "keep_positions" => options.keep_positions = value.extract()?,
+            "element_names" => options.element_names = element_names(value.extract()?)?,
+            key => {
+                return Err(PyErr::new::<PyTypeError, _>(format!(
+                    "unexpected keyword argument {key:?}"
+                )))
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// `ElementNames` of a dictionary naming the `point`, `position` and/or `resolution` elements, the others keeping their
+/// default name.
+fn element_names(names: HashMap<String, String>) -> PyResult<parsers::ElementNames> {
+    let mut element_names = parsers::ElementNames::default();
+    for (element, name) in names {
+        match element.as_str() {
+            "point" => element_names.point = name,
+            "position" => element_names.position = name,
+            "resolution" => element_names.resolution = name,
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(format!(
+                    "element_names keys must be 'point', 'position' or 'resolution', got {element:?}"
+                )))
+            }
+        }
+    }
+    Ok(element_names)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_generic", signature = (xml_text, labels, metadata, period_name, **options))]
+fn parse_timeseries_generic_py(
+    xml_text: &str,
+    labels: Vec<String>,
+    metadata: Vec<String>,
+    period_name: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<HashMap<String, Vec<parsers::Data>>> {
+    let labels: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let metadata: Vec<&str> = metadata.iter().map(|s| s.as_str()).collect();
+    let options = parse_options(options)?;
+
+    parsers::parse_timeseries_generic_with_options(xml_text, labels, metadata, period_name, &options).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_from_path", signature = (path, labels, metadata, period_name, **options))]
+fn parse_timeseries_from_path_py(
+    path: &str,
+    labels: Vec<String>,
+    metadata: Vec<String>,
+    period_name: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<HashMap<String, Vec<parsers::Data>>> {
+    let labels: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let metadata: Vec<&str> = metadata.iter().map(|s| s.as_str()).collect();
+    let options = parse_options(options)?;
+
+    parsers::parse_timeseries_from_path(path, labels, metadata, period_name, &options).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_from_bytes", signature = (data, labels, metadata, period_name, **options))]
+fn parse_timeseries_from_bytes_py(
+    data: &[u8],
+    labels: Vec<String>,
+    metadata: Vec<String>,
+    period_name: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<HashMap<String, Vec<parsers::Data>>> {
+    let labels: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let metadata: Vec<&str> = metadata.iter().map(|s| s.as_str()).collect();
+    let options = parse_options(options)?;
+
+    parsers::parse_timeseries_from_bytes(data, labels, metadata, period_name, &options).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_document", signature = (xml_text, labels, metadata, period_name, **options))]
+fn parse_timeseries_document_py(
+    xml_text: &str,
+    labels: Vec<String>,
+    metadata: Vec<String>,
+    period_name: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<parsers::Document> {
+    let labels: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let metadata: Vec<&str> = metadata.iter().map(|s| s.as_str()).collect();
+    let options = parse_options(options)?;
+
+    parsers::try_parse_timeseries_document(xml_text, labels, metadata, period_name, &options).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_multidoc", signature = (xml_text, labels, metadata, period_name, **options))]
+fn parse_timeseries_multidoc_py(
+    xml_text: &str,
+    labels: Vec<String>,
+    metadata: Vec<String>,
+    period_name: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<parsers::Document> {
+    let labels: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let metadata: Vec<&str> = metadata.iter().map(|s| s.as_str()).collect();
+    let options = parse_options(options)?;
+
+    parsers::parse_timeseries_multidoc(xml_text, labels, metadata, period_name, &options).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(
+    name = "parse_timeseries_multi",
+    signature = (xml_text, labels, period_name, separator="_", series_index=false, positions=false)
+)]
+fn parse_timeseries_multi_py(
+    xml_text: &str,
+    labels: Vec<String>,
+    period_name: &str,
+    separator: &str,
+    series_index: bool,
+    positions: bool,
+) -> PyResult<HashMap<String, Vec<parsers::Data>>> {
+    let labels: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+    let keys = parsers::KeyFormat {
+        separator: separator.to_string(),
+        series_index,
+        positions,
+    };
+
+    parsers::parse_timeseries_multi_with_keys(xml_text, &labels, period_name, &keys).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "for_each_point")]
+fn for_each_point_py(xml_text: &str, label: &str, period_name: &str, callback: &Bound<'_, PyAny>) -> PyResult<()> {
+    // An exception raised by the callback stops the parsing, and is raised once it returns.
+    let mut callback_err: Option<PyErr> = None;
+    parsers::for_each_point(xml_text, label, period_name, |resolution, timestamp, value| {
+        match callback.call1((resolution, timestamp, value.clone())) {
+            // Only an explicit `False` stops, so that callbacks returning `None` go through the whole document.
+            Ok(result) if result.is(PyBool::new(callback.py(), false)) => ControlFlow::Break(()),
+            Ok(_) => ControlFlow::Continue(()),
+            Err(e) => {
+                callback_err = Some(e);
+                ControlFlow::Break(())
+            }
+        }
+    })
+    .map_err(to_py_err)?;
+    callback_err.map_or(Ok(()), Err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_columns")]
+fn parse_timeseries_columns_py(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+) -> PyResult<HashMap<String, parsers::Columns>> {
+    parsers::parse_timeseries_columns(xml_text, label, period_name).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_nth")]
+fn parse_timeseries_nth_py(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+    index: usize,
+) -> PyResult<parsers::TimeSeries> {
+    parsers::parse_timeseries_nth(xml_text, label, period_name, index).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_resampled", signature = (xml_text, label, period_name, aggregation="mean"))]
+fn parse_timeseries_resampled_py(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+    aggregation: &str,
+) -> PyResult<parsers::Columns> {
+    let aggregation = match aggregation {
+        "mean" => parsers::Aggregation::Mean,
+        "sum" => parsers::Aggregation::Sum,
+        _ => {
+            return Err(PyErr::new::<PyValueError, _>(format!(
+                "aggregation must be 'mean' or 'sum', got {aggregation:?}"
+            )))
+        }
+    };
+    parsers::parse_timeseries_resampled(xml_text, label, period_name, aggregation).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_records")]
+fn parse_timeseries_records_py(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+) -> PyResult<Vec<HashMap<String, parsers::Data>>> {
+    parsers::parse_timeseries_records(xml_text, label, period_name).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_auto")]
+fn parse_auto_py(xml_text: &str) -> PyResult<HashMap<String, Vec<parsers::Data>>> {
+    parsers::parse_auto(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_to_csv")]
+fn parse_timeseries_to_csv_py(xml_text: &str, label: &str, period_name: &str) -> PyResult<String> {
+    parsers::parse_timeseries_to_csv(xml_text, label, period_name).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_timeseries_by_psr_type")]
+fn parse_timeseries_by_psr_type_py(
+    xml_text: &str,
+    label: &str,
+    period_name: &str,
+) -> PyResult<HashMap<String, parsers::Columns>> {
+    parsers::parse_timeseries_by_psr_type(xml_text, label, period_name).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_load")]
+fn parse_load_py(xml_text: &str) -> PyResult<parsers::Load> {
+    parsers::parse_load(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_net_transfer_capacity")]
+fn parse_net_transfer_capacity_py(xml_text: &str) -> PyResult<Vec<parsers::TransferCapacity>> {
+    parsers::parse_net_transfer_capacity(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_total_load_forecast")]
+fn parse_total_load_forecast_py(xml_text: &str) -> PyResult<parsers::Load> {
+    parsers::parse_total_load_forecast(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_actual_total_load")]
+fn parse_actual_total_load_py(xml_text: &str) -> PyResult<parsers::Load> {
+    parsers::parse_actual_total_load(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_activated_energy")]
+fn parse_activated_energy_py(xml_text: &str) -> PyResult<Vec<parsers::ActivatedEnergy>> {
+    parsers::parse_activated_energy(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_reservoir")]
+fn parse_reservoir_py(xml_text: &str) -> PyResult<parsers::Reservoir> {
+    parsers::parse_reservoir(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_generation_forecast", signature = (xml_text, align=false))]
+fn parse_generation_forecast_py(xml_text: &str, align: bool) -> PyResult<parsers::GenerationForecast> {
+    parsers::parse_generation_forecast(xml_text, align).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_installed_capacity")]
+fn parse_installed_capacity_py(xml_text: &str) -> PyResult<HashMap<String, parsers::Columns>> {
+    parsers::parse_installed_capacity(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_imbalance_prices")]
+fn parse_imbalance_prices_py(xml_text: &str) -> PyResult<parsers::ImbalancePrices> {
+    parsers::parse_imbalance_prices(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_scheduled_exchanges")]
+fn parse_scheduled_exchanges_py(xml_text: &str) -> PyResult<Vec<parsers::Flow>> {
+    parsers::parse_scheduled_exchanges(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_flows")]
+fn parse_flows_py(xml_text: &str, label: &str, period_name: &str) -> PyResult<parsers::Flows> {
+    parsers::parse_flows(xml_text, label, period_name).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_unavailability")]
+fn parse_unavailability_py(xml_text: &str) -> PyResult<Vec<parsers::Outage>> {
+    parsers::parse_unavailability(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "psr_type_name")]
+fn psr_type_name_py(code: &str) -> Option<&'static str> {
+    codes::psr_type_name(code)
+}
+
+#[pyfunction]
+#[pyo3(name = "business_type_name")]
+fn business_type_name_py(code: &str) -> Option<&'static str> {
+    codes::business_type_name(code)
+}
+
+#[pyfunction]
+#[pyo3(name = "unit_name")]
+fn unit_name_py(code: &str) -> Option<&'static str> {
+    codes::unit_name(code)
+}
+
+#[pyfunction]
+#[pyo3(name = "convert_unit")]
+fn convert_unit_py(value: f64, from_unit: &str, to_unit: &str) -> Option<f64> {
+    codes::convert_unit(value, from_unit, to_unit)
+}
+
+#[pyfunction]
+#[pyo3(name = "process_type_name")]
+fn process_type_name_py(code: &str) -> Option<&'static str> {
+    codes::process_type_name(code)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_acknowledgement")]
+fn parse_acknowledgement_py(xml_text: &str) -> PyResult<Option<parsers::Reason>> {
+    parsers::parse_acknowledgement(xml_text).map_err(to_py_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "check_status")]
+fn check_status_py(status: u16) -> PyResult<()> {
+    match parsers::DenkiError::from_status(status) {
+        Some(e) => Err(denki_error(&e)),
+        None => Ok(()),
+    }
+}
+
+/// A Python module implemented in Rust. The name of this function must match
+/// the `lib.name` setting in the `Cargo.toml`, else Python will not be able to
+/// import the module.
+#[pymodule]
+fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    for name in [
+        "DenkiParseError",
+        "DenkiHttpError",
+        "DenkiRateLimitedError",
+        "DenkiUnsupportedError",
+    ] {
+        m.add(name, client_exception(m.py(), name)?)?;
+    }
+    m.add_function(wrap_pyfunction!(parse_timeseries_generic_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_from_path_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_from_bytes_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_document_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_multidoc_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_multi_py, m)?)?;
+    m.add_function(wrap_pyfunction!(for_each_point_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_columns_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_nth_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_resampled_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_records_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_auto_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_to_csv_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_timeseries_by_psr_type_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_load_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_total_load_forecast_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_actual_total_load_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_reservoir_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_activated_energy_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_net_transfer_capacity_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_generation_forecast_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_installed_capacity_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_imbalance_prices_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_scheduled_exchanges_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_flows_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_unavailability_py, m)?)?;
+    m.add_function(wrap_pyfunction!(psr_type_name_py, m)?)?;
+    m.add_function(wrap_pyfunction!(business_type_name_py, m)?)?;
+    m.add_function(wrap_pyfunction!(process_type_name_py, m)?)?;
+    m.add_function(wrap_pyfunction!(unit_name_py, m)?)?;
+    m.add_function(wrap_pyfunction!(convert_unit_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_acknowledgement_py, m)?)?;
+    m.add_function(wrap_pyfunction!(check_status_py, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Uses the parsers as a Rust library, e.g. with `cargo test --no-default-features` to build them without pyo3.
+use _core::parsers::{parse_timeseries_generic, Data};
+
+#[test]
+fn test_parse_timeseries_generic() {
+    let xml_text = r#"<?xml version="1.0" encoding="utf-8"?>
+    <Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+    <TimeSeries>
+        <Period>
+            <timeInterval>
+                <start>2023-12-31T23:00Z</start>
+                <end>2024-01-01T01:00Z</end>
+            </timeInterval>
+            <resolution>PT60M</resolution>
+            <Point>
+                <position>1</position>
+                <price.amount>104.98</price.amount>
+            </Point>
+            <Point>
+                <position>2</position>
+                <price.amount>105.98</price.amount>
+            </Point>
+        </Period>
+    </TimeSeries>
+    </Publication_MarketDocument>
+    "#;
+
+    let data = parse_timeseries_generic(xml_text, vec!["price.amount"], vec![], "period").unwrap();
+    assert_eq!(
+        data["timestamp"],
+        vec![
+            Data::Timestamp("2023-12-31T23:00:00Z".parse().unwrap()),
+            Data::Timestamp("2024-01-01T00:00:00Z".parse().unwrap()),
+        ]
+    );
+    assert_eq!(data["price.amount"], vec![Data::F64(104.98), Data::F64(105.98)]);
+}
@@ -0,0 +1,31 @@
+import pytest
+
+from denki_client.area import Area, DocumentType
+
+
+@pytest.mark.parametrize("document_type", list(DocumentType))
+def test_document_type_round_trip(document_type: DocumentType):
+    assert DocumentType.from_code(document_type.as_code()) is document_type
+
+
+def test_document_type_from_invalid_code():
+    with pytest.raises(ValueError):
+        DocumentType.from_code("Z99")
+
+
+@pytest.mark.parametrize(
+    "name, eic_code",
+    [
+        ("FR", "10YFR-RTE------C"),
+        ("DE_LU", "10Y1001A1001A82H"),
+        ("NO_2", "10YNO-2--------T"),
+        ("no_2", "10YNO-2--------T"),
+    ],
+)
+def test_area_from_name(name: str, eic_code: str):
+    assert Area.from_name(name).eic_code == eic_code
+
+
+def test_area_from_invalid_name():
+    with pytest.raises(ValueError):
+        Area.from_name("Atlantis")
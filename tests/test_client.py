@@ -0,0 +1,510 @@
+import asyncio
+import gzip
+import io
+import zipfile
+from datetime import UTC, datetime, timedelta
+
+import httpx
+import pytest
+
+from denki_client.area import Area, DocumentType, PsrType
+from denki_client.entsoe import EntsoeClient, fetch_and_parse
+from denki_client.exceptions import (
+    DenkiAuthError,
+    DenkiTimeoutError,
+    EntsoeResponseError,
+    InvalidBusinessParameterError,
+    InvalidParameterError,
+)
+from tests.test_parsers import DOCUMENT
+
+
+def mock_client(handler, **kwargs) -> EntsoeClient:
+    return EntsoeClient("API_KEY", backend="polars", transport=httpx.MockTransport(handler), **kwargs)
+
+
+async def test_get_document():
+    """Test that the security token is added to the query parameters."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(200, text="<Publication_MarketDocument/>")
+
+    client = mock_client(handler)
+    document = await client.get_document({"documentType": "A44", "in_Domain": "10YFR-RTE------C"})
+
+    assert document == "<Publication_MarketDocument/>"
+    assert requests[0].url.host == "web-api.tp.entsoe.eu"
+    assert requests[0].url.params["securityToken"] == "API_KEY"
+    assert requests[0].url.params["documentType"] == "A44"
+
+
+async def test_get_document_base_url():
+    """Test that requests are sent to the configured base URL."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(200, text="<Publication_MarketDocument/>")
+
+    client = mock_client(handler, base_url="http://localhost:8080/entsoe/api")
+    await client.get_document({"documentType": "A44"})
+
+    assert str(requests[0].url) == "http://localhost:8080/entsoe/api?documentType=A44&securityToken=API_KEY"
+
+
+async def test_get_document_enum_params():
+    """Test that enum parameters are sent as their ENTSO-e codes."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(200, text="<GL_MarketDocument/>")
+
+    client = mock_client(handler)
+    await client.get_document({"documentType": DocumentType.A75, "psrType": PsrType.B16, "in_Domain": Area.FR})
+
+    assert requests[0].url.params["documentType"] == "A75"
+    assert requests[0].url.params["psrType"] == "B16"
+    assert requests[0].url.params["in_Domain"] == "10YFR-RTE------C"
+
+
+async def test_get_document_concurrent():
+    """Test that requests run concurrently on the same connection pool."""
+    requests = []
+    both_sent = asyncio.Event()
+
+    async def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        if len(requests) == 2:
+            both_sent.set()
+        # The first request only completes once the second one has been sent.
+        await asyncio.wait_for(both_sent.wait(), timeout=1)
+        return httpx.Response(200, text=f"<{request.url.params['documentType']}/>")
+
+    async with mock_client(handler) as client:
+        documents = await asyncio.gather(
+            client.get_document({"documentType": "A44"}),
+            client.get_document({"documentType": "A75"}),
+        )
+
+    assert documents == ["<A44/>", "<A75/>"]
+    assert client.session.is_closed
+
+
+async def test_http_client():
+    """Test that an existing httpx client is reused with its headers, and left open."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(200, text="<Publication_MarketDocument/>")
+
+    http_client = httpx.AsyncClient(headers={"User-Agent": "my-app/1.0"}, transport=httpx.MockTransport(handler))
+    async with EntsoeClient("API_KEY", backend="polars", http_client=http_client) as client:
+        await client.get_document({"documentType": "A44"})
+
+    assert requests[0].headers["user-agent"] == "my-app/1.0"
+    assert not http_client.is_closed
+    with pytest.raises(TypeError):
+        EntsoeClient("API_KEY", backend="polars", http_client=http_client, timeout=10)
+    await http_client.aclose()
+
+
+def test_available_document_types():
+    """Test that France supports day-ahead prices and load, along with the cross-border documents."""
+    document_types = EntsoeClient.available_document_types("FR")
+
+    assert DocumentType.A44 in document_types
+    assert DocumentType.A65 in document_types
+    assert DocumentType.A11 in document_types
+    assert DocumentType.A11 not in EntsoeClient.available_document_types(Area.CY)
+
+
+async def test_get_document_cache(tmp_path):
+    """Test that an identical request is served from the cache, until it expires."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(200, text=f"<Publication_MarketDocument>{len(requests)}</Publication_MarketDocument>")
+
+    client = mock_client(handler).with_cache(tmp_path)
+    first = await client.get_document({"documentType": "A44", "in_Domain": "10YFR-RTE------C"})
+    second = await client.get_document({"in_Domain": "10YFR-RTE------C", "documentType": "A44"})
+    other = await client.get_document({"documentType": "A65", "in_Domain": "10YFR-RTE------C"})
+
+    assert first == second == "<Publication_MarketDocument>1</Publication_MarketDocument>"
+    assert other == "<Publication_MarketDocument>2</Publication_MarketDocument>"
+    assert len(requests) == 2
+    assert all("API_KEY" not in path.name for path in tmp_path.iterdir())
+
+    client = mock_client(handler).with_cache(tmp_path, ttl=timedelta(0))
+    expired = await client.get_document({"documentType": "A44", "in_Domain": "10YFR-RTE------C"})
+
+    assert expired == "<Publication_MarketDocument>3</Publication_MarketDocument>"
+
+
+async def test_get_document_cache_acknowledgement(tmp_path):
+    """Test that an acknowledgement served with a 200 status is not cached."""
+    acknowledgement = """<?xml version="1.0" encoding="utf-8"?>
+<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+    <Reason>
+        <code>999</code>
+        <text>Service is temporarily unavailable</text>
+    </Reason>
+</Acknowledgement_MarketDocument>
+"""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(200, text=acknowledgement, headers={"content-type": "application/xml"})
+
+    client = mock_client(handler).with_cache(tmp_path)
+    assert await client.get_document({"documentType": "A44"}) == acknowledgement
+    assert await client.get_document({"documentType": "A44"}) == acknowledgement
+
+    assert len(requests) == 2
+    assert list(tmp_path.iterdir()) == []
+
+
+async def test_get_document_error_status():
+    """Test that an error status is raised with its status code and body."""
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        return httpx.Response(503, text="Service Unavailable")
+
+    client = mock_client(handler, retry_base_delay=0, retry_jitter=0)
+    with pytest.raises(EntsoeResponseError) as exc_info:
+        await client.get_document({"documentType": "A44"})
+
+    assert exc_info.value.status_code == 503
+    assert exc_info.value.body == "Service Unavailable"
+
+
+async def test_get_document_retry_too_many_requests():
+    """Test that a 429 status is retried."""
+    statuses = iter([429, 200])
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        return httpx.Response(next(statuses), text="<Publication_MarketDocument/>")
+
+    client = mock_client(handler, retry_base_delay=0, retry_jitter=0)
+    document = await client.get_document({"documentType": "A44"})
+
+    assert document == "<Publication_MarketDocument/>"
+    assert next(statuses, None) is None
+
+
+async def test_get_document_retry_unavailable():
+    """Test that transient 503 statuses are retried."""
+    statuses = iter([503, 503, 200])
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        return httpx.Response(next(statuses), text="<Publication_MarketDocument/>")
+
+    client = mock_client(handler, retry_base_delay=0, retry_jitter=0)
+    document = await client.get_document({"documentType": "A44"})
+
+    assert document == "<Publication_MarketDocument/>"
+    assert next(statuses, None) is None
+
+
+async def test_get_document_not_retried():
+    """Test that a 401 status fails without retrying."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(401, text="Unauthorized")
+
+    client = mock_client(handler, retry_base_delay=0, retry_jitter=0)
+    with pytest.raises(EntsoeResponseError) as exc_info:
+        await client.get_document({"documentType": "A44"})
+
+    assert exc_info.value.status_code == 401
+    assert exc_info.value.attempts == 1
+    assert len(requests) == 1
+
+
+async def test_get_document_unauthorized():
+    """Test that a 401 status raises a DenkiAuthError pointing to the security token."""
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        return httpx.Response(401, text="<html><body>Unauthorized</body></html>")
+
+    client = mock_client(handler)
+    with pytest.raises(DenkiAuthError, match="check the security token") as exc_info:
+        await client.get_document({"documentType": "A44"})
+
+    assert exc_info.value.status_code == 401
+    assert exc_info.value.body == "<html><body>Unauthorized</body></html>"
+
+
+def test_empty_api_key():
+    """Test that an empty security token is rejected when creating the client."""
+    for api_key in ["", "  "]:
+        with pytest.raises(InvalidParameterError, match="api_key is empty"):
+            EntsoeClient(api_key, backend="polars")
+
+
+async def test_get_document_retry_exhausted():
+    """Test that the last 429 status is raised once every attempt failed."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        return httpx.Response(429, text="Too Many Requests")
+
+    client = mock_client(handler, max_attempts=2, retry_base_delay=0, retry_jitter=0)
+    with pytest.raises(EntsoeResponseError) as exc_info:
+        await client.get_document({"documentType": "A44"})
+
+    assert exc_info.value.status_code == 429
+    assert exc_info.value.attempts == 2
+    assert "status 429 after 2 attempts" in str(exc_info.value)
+    assert len(requests) == 2
+
+
+async def test_get_document_timeout():
+    """Test that the timeout is set on the httpx client, and that a request timing out raises a DenkiTimeoutError."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        raise httpx.ReadTimeout("timed out", request=request)
+
+    assert mock_client(handler).session.timeout == httpx.Timeout(30.0, connect=10.0)
+    client = mock_client(handler, timeout=2.5, retry_base_delay=0, retry_jitter=0)
+    assert client.session.timeout == httpx.Timeout(2.5)
+
+    with pytest.raises(DenkiTimeoutError) as exc_info:
+        await client.get_document({"documentType": "A44"})
+
+    assert exc_info.value.attempts == 3
+    assert "timed out after 3 attempts (ReadTimeout)" in str(exc_info.value)
+    assert len(requests) == 3
+
+
+async def test_parse_zip_response():
+    """Test that the documents of a ZIP response are merged in chronological order."""
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w") as archive:
+        archive.writestr("001-document.xml", DOCUMENT.format(start="2024-01-01T23:00Z", price=105.98))
+        archive.writestr("002-document.xml", DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98))
+    response = httpx.Response(200, content=buffer.getvalue(), headers={"content-type": "application/zip"})
+
+    client = mock_client(lambda request: response)
+    data = client._parse_response(response, ["price.amount"], [], "period")
+
+    assert data["timestamp"] == [datetime(2023, 12, 31, 23, tzinfo=UTC), datetime(2024, 1, 1, 23, tzinfo=UTC)]
+    assert data["price.amount"] == [104.98, 105.98]
+
+
+async def test_gzip_response():
+    """Test that gzip is advertised and that gzip-encoded XML and ZIP bodies are decompressed before parsing."""
+    document = DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98)
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w") as archive:
+        archive.writestr("001-document.xml", document)
+    bodies = {"A44": (document.encode(), "application/xml"), "A65": (buffer.getvalue(), "application/zip")}
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        assert "gzip" in request.headers["accept-encoding"]
+        content, content_type = bodies[request.url.params["documentType"]]
+        return httpx.Response(
+            200, content=gzip.compress(content), headers={"content-encoding": "gzip", "content-type": content_type}
+        )
+
+    client = mock_client(handler)
+    assert await client.get_document({"documentType": "A44"}) == document
+
+    response = await client._get({"documentType": "A65"})
+    data = client._parse_response(response, ["price.amount"], [], "period")
+    assert data["price.amount"] == [104.98]
+
+
+async def test_download_to(tmp_path):
+    """Test that the raw body is written to the file, and that an error status is raised without writing it."""
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w") as archive:
+        archive.writestr("001-document.xml", DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98))
+    archive = buffer.getvalue()
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        if request.url.params["documentType"] == "A65":
+            return httpx.Response(400, text="<Acknowledgement_MarketDocument/>")
+        assert request.url.params["securityToken"] == "API_KEY"
+        return httpx.Response(200, content=archive, headers={"content-type": "application/zip"})
+
+    client = mock_client(handler)
+    path, size = await client.download_to(tmp_path / "response.zip", {"documentType": "A44"})
+
+    assert path == tmp_path / "response.zip"
+    assert size == len(archive)
+    assert path.read_bytes() == archive
+
+    with pytest.raises(EntsoeResponseError):
+        await client.download_to(tmp_path / "error.zip", {"documentType": "A65"})
+    assert not (tmp_path / "error.zip").exists()
+
+
+async def test_query_raw():
+    """Test that the status and headers of the response are returned, even for an error status."""
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        assert request.url.params["securityToken"] == "API_KEY"
+        return httpx.Response(
+            400,
+            text="<Acknowledgement_MarketDocument/>",
+            headers={"content-type": "application/xml", "x-request-id": "42"},
+        )
+
+    client = mock_client(handler)
+    response = await client.query_raw({"documentType": "A44"})
+
+    assert response.status == 400
+    assert response.headers["X-Request-Id"] == "42"
+    assert response.headers["content-type"] == "application/xml"
+    assert response.body == b"<Acknowledgement_MarketDocument/>"
+
+
+async def test_get_timeseries_split_by_year():
+    """Test that a range exceeding a year is queried as contiguous yearly windows."""
+    windows = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        windows.append((request.url.params["periodStart"], request.url.params["periodEnd"]))
+        return httpx.Response(200, text=DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98))
+
+    client = mock_client(handler)
+    df = await client.get_timeseries(
+        "FR", DocumentType.A44, start="2021-01-01T00:00+01:00", end="2023-07-01T00:00+02:00", labels=["price.amount"]
+    )
+
+    assert windows == [
+        ("202101010000", "202201010000"),
+        ("202201010000", "202301010000"),
+        ("202301010000", "202307010000"),
+    ]
+    assert df is not None
+
+
+async def test_fetch_many():
+    """Test that every area is queried, one without data mapping to None and the failure of one being returned
+    without failing the others."""
+    acknowledgement = """<?xml version="1.0" encoding="utf-8"?>
+<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+    <Reason>
+        <code>999</code>
+        <text>{text}</text>
+    </Reason>
+</Acknowledgement_MarketDocument>
+"""
+    domains = []
+
+    headers = {"content-type": "application/xml"}
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        domains.append(request.url.params["in_Domain"])
+        if request.url.params["in_Domain"] == Area.BE.code:
+            text = "No matching data found for Data item Day-ahead Prices [12.1.D] (10YBE----------2)"
+            return httpx.Response(200, text=acknowledgement.format(text=text), headers=headers)
+        if request.url.params["in_Domain"] == Area.NL.code:
+            text = "Please check you request against dependency tables"
+            return httpx.Response(400, text=acknowledgement.format(text=text), headers=headers)
+        return httpx.Response(200, text=DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98))
+
+    client = mock_client(handler)
+    results = await client.fetch_many(
+        DocumentType.A44,
+        ["FR", "BE", "NL"],
+        start="2024-01-01T00:00+01:00",
+        end="2024-01-02T00:00+01:00",
+        labels=["price.amount"],
+        concurrency=1,
+    )
+
+    assert sorted(domains) == sorted([Area.FR.code, Area.BE.code, Area.NL.code])
+    assert list(results) == ["FR", "BE", "NL"]
+    assert results["FR"] is not None
+    assert not isinstance(results["FR"], Exception)
+    assert results["BE"] is None
+    assert isinstance(results["NL"], InvalidBusinessParameterError)
+
+    with pytest.raises(InvalidParameterError):
+        await client.fetch_many(
+            DocumentType.A44, ["FR"], start=datetime.now(UTC), end=datetime.now(UTC), labels=[], concurrency=0
+        )
+
+
+def test_fetch_and_parse():
+    """Test that a blocking call queries, parses and merges the yearly windows in one DataFrame."""
+    requests = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        start = "2023-12-31T23:00Z" if request.url.params["periodStart"] == "202312312300" else "2024-12-31T23:00Z"
+        return httpx.Response(200, text=DOCUMENT.format(start=start, price=104.98))
+
+    df = fetch_and_parse(
+        "API_KEY",
+        DocumentType.A44,
+        "FR",
+        start="2024-01-01T00:00+01:00",
+        end="2025-01-02T00:00+01:00",
+        labels=["price.amount"],
+        backend="polars",
+        transport=httpx.MockTransport(handler),
+    )
+
+    assert len(requests) == 2
+    assert requests[0].url.params["documentType"] == "A44"
+    assert df is not None
+
+
+def test_fetch_and_parse_clip():
+    """Test that a point of a period extending past the requested end is dropped when clipping."""
+    document = """<?xml version="1.0" encoding="utf-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+<TimeSeries>
+    <Period>
+        <timeInterval>
+            <start>2023-12-31T23:00Z</start>
+            <end>2024-01-01T02:00Z</end>
+        </timeInterval>
+        <resolution>PT60M</resolution>
+        <Point>
+            <position>1</position>
+            <price.amount>104.98</price.amount>
+        </Point>
+        <Point>
+            <position>2</position>
+            <price.amount>105.98</price.amount>
+        </Point>
+        <Point>
+            <position>3</position>
+            <price.amount>106.98</price.amount>
+        </Point>
+    </Period>
+</TimeSeries>
+</Publication_MarketDocument>
+"""
+    kwargs = {
+        "start": "2024-01-01T00:00+01:00",
+        "end": "2024-01-01T02:00+01:00",
+        "labels": ["price.amount"],
+        "backend": "polars",
+        "transport": httpx.MockTransport(lambda request: httpx.Response(200, text=document)),
+    }
+
+    df = fetch_and_parse("API_KEY", DocumentType.A44, "FR", **kwargs)
+    assert df is not None
+    assert df["price.amount"].to_list() == [104.98, 105.98, 106.98]
+
+    df = fetch_and_parse("API_KEY", DocumentType.A44, "FR", clip=True, **kwargs)
+    assert df is not None
+    assert df["timestamp"].to_list() == [datetime(2023, 12, 31, 23, tzinfo=UTC), datetime(2024, 1, 1, tzinfo=UTC)]
+    assert df["price.amount"].to_list() == [104.98, 105.98]
@@ -0,0 +1,14 @@
+import ast
+import inspect
+from pathlib import Path
+
+from denki_client import _core
+
+
+def test_stub_matches_module():
+    """Test that the `_core.pyi` stub declares every function of the compiled module."""
+    stub = ast.parse(Path(_core.__file__).with_name("_core.pyi").read_text())
+    stubbed = {node.name for node in stub.body if isinstance(node, ast.FunctionDef)}
+    functions = {name for name, member in inspect.getmembers(_core) if inspect.isbuiltin(member)}
+
+    assert stubbed == functions
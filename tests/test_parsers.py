@@ -0,0 +1,216 @@
+import io
+import zipfile
+from datetime import UTC, datetime
+
+import pytest
+
+from denki_client._core import (
+    DenkiHttpError,
+    DenkiParseError,
+    DenkiRateLimitedError,
+    check_status,
+    for_each_point,
+    parse_timeseries_document,
+    parse_timeseries_from_bytes,
+    parse_timeseries_generic,
+)
+from denki_client.exceptions import DenkiAuthError, EntsoeResponseError, NoMatchingDataError, ParseError
+from denki_client.parsers import split_documents
+
+DOCUMENT = """<?xml version="1.0" encoding="utf-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+<TimeSeries>
+    <Period>
+        <timeInterval>
+            <start>{start}</start>
+        </timeInterval>
+        <resolution>PT60M</resolution>
+        <Point>
+            <position>1</position>
+            <price.amount>{price}</price.amount>
+        </Point>
+    </Period>
+</TimeSeries>
+</Publication_MarketDocument>
+"""
+
+
+def test_split_documents_single_xml():
+    """Test that a XML body is returned as a single document."""
+    document = DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98)
+    assert split_documents(document.encode()) == [document]
+
+
+def test_split_documents_zip():
+    """Test that every XML member of a ZIP archive is returned."""
+    first = DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98)
+    second = DOCUMENT.format(start="2024-01-01T23:00Z", price=105.98)
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w") as archive:
+        archive.writestr("001-document.xml", first)
+        archive.writestr("002-document.xml", second)
+
+    assert split_documents(buffer.getvalue()) == [first, second]
+
+
+def test_parse_truncated_document():
+    """Test that a malformed document raises a `DenkiParseError` giving its location."""
+    document = DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98)[:-60]
+    with pytest.raises(DenkiParseError) as excinfo:
+        parse_timeseries_generic(document, ["price.amount"], [], "period")
+
+    assert excinfo.value.line > 0
+    assert excinfo.value.column > 0
+    assert excinfo.value.message
+    assert isinstance(excinfo.value, ValueError)
+    assert isinstance(excinfo.value, ParseError)
+
+
+def test_error_types():
+    """Test that a malformed document and a rate-limited response raise distinct exception types, which are those of
+    the client for error statuses."""
+    document = DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98)[:-60]
+    with pytest.raises(DenkiParseError):
+        parse_timeseries_generic(document, ["price.amount"], [], "period")
+
+    with pytest.raises(DenkiRateLimitedError) as excinfo:
+        check_status(429)
+    assert excinfo.value.status == 429
+    assert isinstance(excinfo.value, DenkiHttpError)
+    assert isinstance(excinfo.value, EntsoeResponseError)
+    assert not isinstance(excinfo.value, DenkiParseError)
+
+    with pytest.raises(DenkiAuthError) as excinfo:
+        check_status(401)
+    assert excinfo.value.status_code == 401
+    with pytest.raises(DenkiHttpError) as excinfo:
+        check_status(503)
+    assert excinfo.value.status == 503
+    assert excinfo.value.status_code == 503
+    check_status(200)
+
+
+def test_parse_acknowledgement():
+    """Test that an acknowledgement raises `NoMatchingDataError` rather than a bare `ValueError`."""
+    acknowledgement = """<?xml version="1.0" encoding="utf-8"?>
+<Acknowledgement_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+    <Reason>
+        <code>999</code>
+        <text>No matching data found for Data item Day-ahead Prices [12.1.D]</text>
+    </Reason>
+</Acknowledgement_MarketDocument>
+"""
+    with pytest.raises(NoMatchingDataError) as excinfo:
+        parse_timeseries_generic(acknowledgement, ["price.amount"], [], "period")
+
+    assert excinfo.value.code == "999"
+    assert excinfo.value.text == "No matching data found for Data item Day-ahead Prices [12.1.D]"
+    assert not isinstance(excinfo.value, ValueError)
+
+
+def test_parse_timestamps():
+    """Test that timestamps are UTC datetimes, or ISO 8601 strings with `iso_timestamps`."""
+    document = DOCUMENT.format(start="2023-12-31T23:00Z", price=104.98)
+
+    data = parse_timeseries_generic(document, ["price.amount"], [], "period")
+    assert data["timestamp"] == [datetime(2023, 12, 31, 23, tzinfo=UTC)]
+    assert isinstance(data["timestamp"][0], datetime)
+    assert data["timestamp"][0].utcoffset().total_seconds() == 0
+
+    data = parse_timeseries_generic(document, ["price.amount"], [], "period", iso_timestamps=True)
+    assert data["timestamp"] == ["2023-12-31T23:00:00Z"]
+
+
+def test_parse_fill_missing():
+    """Test that gap-filled positions come back as None."""
+    document = """<?xml version="1.0" encoding="utf-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+<TimeSeries>
+    <curveType>A01</curveType>
+    <Period>
+        <timeInterval>
+            <start>2023-12-31T23:00Z</start>
+        </timeInterval>
+        <resolution>PT60M</resolution>
+        <Point>
+            <position>1</position>
+            <price.amount>104.98</price.amount>
+        </Point>
+        <Point>
+            <position>3</position>
+            <price.amount>105.98</price.amount>
+        </Point>
+    </Period>
+</TimeSeries>
+</Publication_MarketDocument>
+"""
+    data = parse_timeseries_generic(document, ["price.amount"], [], "period", fill_missing=True)
+
+    assert data["price.amount"] == [104.98, None, 105.98]
+    assert data["timestamp"][1] == datetime(2024, 1, 1, tzinfo=UTC)
+
+
+def test_for_each_point():
+    """Test that the callback is called on each point, until it returns False or raises."""
+    document = """<?xml version="1.0" encoding="utf-8"?>
+<Publication_MarketDocument xmlns="urn:iec62325.351:tc57wg16:451-3:publicationdocument:7:3">
+<TimeSeries>
+    <Period>
+        <timeInterval>
+            <start>2023-12-31T23:00Z</start>
+        </timeInterval>
+        <resolution>PT60M</resolution>
+        <Point>
+            <position>1</position>
+            <price.amount>104.98</price.amount>
+        </Point>
+        <Point>
+            <position>2</position>
+            <price.amount>2.5</price.amount>
+        </Point>
+        <Point>
+            <position>3</position>
+            <price.amount>3.5</price.amount>
+        </Point>
+    </Period>
+</TimeSeries>
+</Publication_MarketDocument>
+"""
+
+    points = []
+    for_each_point(document, "price.amount", "Period", lambda *point: points.append(point))
+    assert points == [
+        ("PT60M", datetime(2023, 12, 31, 23, tzinfo=UTC), 104.98),
+        ("PT60M", datetime(2024, 1, 1, 0, tzinfo=UTC), 2.5),
+        ("PT60M", datetime(2024, 1, 1, 1, tzinfo=UTC), 3.5),
+    ]
+
+    points = []
+    for_each_point(document, "price.amount", "Period", lambda *point: points.append(point) or len(points) < 2)
+    assert len(points) == 2
+
+    def callback(*point):
+        raise KeyError(point[0])
+
+    with pytest.raises(KeyError):
+        for_each_point(document, "price.amount", "Period", callback)
+
+
+def test_parse_options():
+    """Test that every parser taking options reads the same keyword options, refusing an unknown one."""
+    document = DOCUMENT.format(start="2023-12-31T23:00Z", price="104,98")
+    document = document.replace("Point>", "Pt>").replace("<position>1</position>", "<position>0</position>")
+    options = {"element_names": {"point": "Pt"}, "position_base": 0, "decimal_comma": True, "keep_positions": True}
+
+    data = parse_timeseries_generic(document, ["price.amount"], [], "period", **options)
+    assert data["price.amount"] == [104.98]
+    assert data["position"] == [0]
+    assert data["timestamp"] == [datetime(2023, 12, 31, 23, tzinfo=UTC)]
+    assert parse_timeseries_from_bytes(document.encode(), ["price.amount"], [], "period", **options) == data
+    series = parse_timeseries_document(document, ["price.amount"], [], "period", **options)["series"]
+    assert series[0]["data"]["price.amount"] == [104.98]
+
+    with pytest.raises(ValueError, match="element_names keys"):
+        parse_timeseries_generic(document, ["price.amount"], [], "period", element_names={"period": "Pt"})
+    with pytest.raises(TypeError, match="unexpected keyword argument"):
+        parse_timeseries_generic(document, ["price.amount"], [], "period", fill_gaps=True)
@@ -0,0 +1,39 @@
+from datetime import UTC, datetime, timedelta, timezone
+
+import pytest
+
+from denki_client.area import Area, DocumentType
+from denki_client.exceptions import InvalidParameterError, TzNaiveError
+from denki_client.query import QueryBuilder
+
+
+def test_query_builder():
+    params = (
+        QueryBuilder()
+        .document_type(DocumentType.A44)
+        .in_domain("FR")
+        .out_domain(Area.FR)
+        .period_start(datetime(2024, 1, 1, tzinfo=timezone(timedelta(hours=1))))
+        .period_end(datetime(2024, 1, 2, tzinfo=UTC))
+        .build()
+    )
+
+    assert params == {
+        "documentType": "A44",
+        "in_Domain": "10YFR-RTE------C",
+        "out_Domain": "10YFR-RTE------C",
+        "periodStart": "202312312300",
+        "periodEnd": "202401020000",
+    }
+
+
+def test_query_builder_missing_field():
+    builder = QueryBuilder().document_type("A65").period_start(datetime(2024, 1, 1, tzinfo=UTC))
+
+    with pytest.raises(InvalidParameterError, match="periodEnd"):
+        builder.build()
+
+
+def test_query_builder_tz_naive():
+    with pytest.raises(TzNaiveError):
+        QueryBuilder().period_start(datetime(2024, 1, 1))
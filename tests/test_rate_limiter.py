@@ -0,0 +1,23 @@
+import time
+
+from denki_client.rate_limiter import RateLimiter
+
+
+async def test_rate_limiter_burst():
+    """Test that calls within the capacity are not delayed."""
+    limiter = RateLimiter(3, period=60)
+    t0 = time.monotonic()
+    for _ in range(3):
+        await limiter.acquire()
+
+    assert time.monotonic() - t0 < 0.05
+
+
+async def test_rate_limiter_paces_calls():
+    """Test that calls exceeding the capacity wait for the bucket to refill."""
+    limiter = RateLimiter(2, period=0.2)
+    t0 = time.monotonic()
+    for _ in range(4):
+        await limiter.acquire()
+
+    assert time.monotonic() - t0 >= 0.19